@@ -0,0 +1,538 @@
+//! Zero-copy, allocation-free track event parsing.
+//!
+//! The owned API in [`events`](crate::events) and [`track`](crate::track) allocates a `String`
+//! for every event name and copies text/sysex payloads into owned buffers, which is wasteful
+//! when bulk-parsing a large file. This module adds a borrowed parsing path: [`TrackEventKind`]
+//! holds `&'a [u8]` slices straight out of the source buffer instead of owned data, and
+//! [`read_borrowed`] advances a `&'a [u8]` cursor in place without allocating. `to_owned_event`
+//! bridges back to the existing `Box<dyn Event>` API for callers that don't need the fast path.
+//!
+//! [`read_borrowed`] also hands back the exact source slice each event was decoded from via
+//! [`BorrowedTrackEvent::raw_bytes`]. Re-encoding a `TrackEventKind` loses anything the decoded
+//! view doesn't model (e.g. vendor bytes inside a `SequencerSpecificEvent`), so callers that need
+//! a byte-identical round trip should write `raw_bytes` back out instead of re-serializing the
+//! decoded fields.
+
+use alloc::{borrow::Cow, string::ToString};
+
+use crate::{
+    events::Event,
+    metadata::TimeDivision,
+    metaevents::from_vlq_to_bytes,
+    midi_error::{MidiError, ParseError},
+    system_exclusive::{SystemExclusiveEvent, SYSTEM_EXCLUSIVE_BYTE},
+    track::{parse_event, parse_meta_event},
+};
+
+/// A MIDI data value known to fit in 7 bits (0..=127).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct U7(u8);
+
+impl U7 {
+    /// Construct a `U7`, validating that `value` fits in 7 bits.
+    pub fn new(value: u8) -> Result<Self, MidiError> {
+        if value & 0x80 != 0 {
+            return Err(range_error("U7", value as u32));
+        }
+        Ok(Self(value))
+    }
+
+    pub fn as_u8(&self) -> u8 {
+        self.0
+    }
+}
+
+/// A MIDI data value made of two 7-bit bytes (LSB first), e.g. pitch bend or song position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct U14(u16);
+
+impl U14 {
+    /// Construct a `U14`, validating that `value` fits in 14 bits.
+    pub fn new(value: u16) -> Result<Self, MidiError> {
+        if value > 0x3FFF {
+            return Err(range_error("U14", value as u32));
+        }
+        Ok(Self(value))
+    }
+
+    /// Construct a `U14` from its little-endian 7-bit data bytes.
+    pub fn from_parts(lsb: u8, msb: u8) -> Result<Self, MidiError> {
+        Self::new(((msb as u16 & 0x7F) << 7) | (lsb as u16 & 0x7F))
+    }
+
+    pub fn as_u16(&self) -> u16 {
+        self.0
+    }
+}
+
+/// A MIDI variable-length quantity, capped at 28 bits (4 VLQ bytes) as used for delta-times and
+/// meta-event/sysex lengths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct U28(u32);
+
+impl U28 {
+    /// Construct a `U28`, validating that `value` fits in 28 bits.
+    pub fn new(value: u32) -> Result<Self, MidiError> {
+        if value > 0x0FFF_FFFF {
+            return Err(range_error("U28", value));
+        }
+        Ok(Self(value))
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        self.0
+    }
+
+    /// Read a VLQ-encoded `U28` off the front of `raw`, advancing the slice past the bytes
+    /// consumed.
+    pub fn read(raw: &mut &[u8]) -> Result<Self, MidiError> {
+        let mut value: u32 = 0;
+
+        for index in 0..4 {
+            let byte = take_byte(raw)?;
+            value = (value << 7) | (byte & 0x7F) as u32;
+
+            if byte & 0x80 == 0 {
+                return Ok(Self(value));
+            }
+
+            if index == 3 {
+                return Err(MidiError::ParseError(ParseError::InvalidEventBytes(
+                    "Variable-length quantity exceeds 28 bits".to_string(),
+                )));
+            }
+        }
+
+        unreachable!("loop above always returns by its fourth iteration")
+    }
+}
+
+/// A single track event, borrowed directly from the source buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackEventKind<'a> {
+    NoteOff {
+        channel: u8,
+        note: U7,
+        velocity: U7,
+    },
+    NoteOn {
+        channel: u8,
+        note: U7,
+        velocity: U7,
+    },
+    PolyphonicKeyPressure {
+        channel: u8,
+        note: U7,
+        pressure: U7,
+    },
+    ControlChange {
+        channel: u8,
+        controller: U7,
+        value: U7,
+    },
+    ProgramChange {
+        channel: u8,
+        program: U7,
+    },
+    ChannelPressure {
+        channel: u8,
+        pressure: U7,
+    },
+    PitchBendChange {
+        channel: u8,
+        value: U14,
+    },
+    /// A meta event (`0xFF <type> <length> <data>`); `data` borrows straight from the source.
+    Meta { kind: u8, data: &'a [u8] },
+    /// A System Exclusive event (`0xF0`/`0xF7`); `data` borrows straight from the source.
+    SysEx(&'a [u8]),
+}
+
+impl<'a> TrackEventKind<'a> {
+    /// Convert this borrowed event back into the crate's existing owned `Box<dyn Event>`
+    /// representation, for callers that don't need the zero-copy fast path.
+    ///
+    /// ### Arguments
+    /// * `delta_time` The event's delta time, as returned alongside this event by `read_borrowed`
+    /// * `time_division` The file's time division, used to compute `time_duration`
+    /// * `tempo` The tempo in effect, used to compute `time_duration`
+    pub fn to_owned_event(
+        &self,
+        delta_time: u32,
+        time_division: TimeDivision,
+        tempo: u32,
+    ) -> Result<Box<dyn Event>, MidiError> {
+        let event = match *self {
+            TrackEventKind::NoteOff { channel, note, velocity } => parse_event(
+                0x80 | channel,
+                &[0x80 | channel, note.as_u8(), velocity.as_u8()],
+                delta_time,
+                time_division,
+                tempo,
+            ),
+            TrackEventKind::NoteOn { channel, note, velocity } => parse_event(
+                0x90 | channel,
+                &[0x90 | channel, note.as_u8(), velocity.as_u8()],
+                delta_time,
+                time_division,
+                tempo,
+            ),
+            TrackEventKind::PolyphonicKeyPressure { channel, note, pressure } => parse_event(
+                0xA0 | channel,
+                &[0xA0 | channel, note.as_u8(), pressure.as_u8()],
+                delta_time,
+                time_division,
+                tempo,
+            ),
+            TrackEventKind::ControlChange { channel, controller, value } => parse_event(
+                0xB0 | channel,
+                &[0xB0 | channel, controller.as_u8(), value.as_u8()],
+                delta_time,
+                time_division,
+                tempo,
+            ),
+            TrackEventKind::ProgramChange { channel, program } => parse_event(
+                0xC0 | channel,
+                &[0xC0 | channel, program.as_u8()],
+                delta_time,
+                time_division,
+                tempo,
+            ),
+            TrackEventKind::ChannelPressure { channel, pressure } => parse_event(
+                0xD0 | channel,
+                &[0xD0 | channel, pressure.as_u8()],
+                delta_time,
+                time_division,
+                tempo,
+            ),
+            TrackEventKind::PitchBendChange { channel, value } => {
+                let value = value.as_u16();
+                parse_event(
+                    0xE0 | channel,
+                    &[0xE0 | channel, (value & 0x7F) as u8, ((value >> 7) & 0x7F) as u8],
+                    delta_time,
+                    time_division,
+                    tempo,
+                )
+            }
+            TrackEventKind::Meta { kind, data } => {
+                let mut bytes = vec![0xFF, kind, data.len() as u8];
+                bytes.extend_from_slice(data);
+                parse_meta_event(&bytes, delta_time, time_division, tempo)
+            }
+            TrackEventKind::SysEx(data) => {
+                // `read_borrowed` doesn't keep track of which status byte (`0xF0` or `0xF7`)
+                // introduced this payload, so it's rebuilt here as a normal (`0xF0`) message.
+                let mut bytes = vec![SYSTEM_EXCLUSIVE_BYTE];
+                bytes.extend_from_slice(&from_vlq_to_bytes(data.len() as u32));
+                bytes.extend_from_slice(data);
+                SystemExclusiveEvent::from_bytes(&bytes, delta_time, time_division, tempo)
+                    .map(crate::events::EventKind::SystemExclusive)
+            }
+        }?;
+
+        Ok(Box::new(event))
+    }
+
+    /// Borrows this event's payload as UTF-8 text without copying, for the text meta events
+    /// (`TrackNameEvent`/`MarkerEvent` and friends) that carry a VLQ-length-prefixed string.
+    ///
+    /// Unlike `TrackNameEvent::from_bytes`/`MarkerEvent::from_bytes`, which always allocate a
+    /// `String`, this returns a `Cow::Borrowed(&'a str)` tied straight to the source buffer;
+    /// callers that need to keep the text past the buffer's lifetime can lift it with
+    /// `Cow::into_owned`. Returns `None` for event kinds that don't carry text.
+    pub fn as_text(&self) -> Option<Result<Cow<'a, str>, MidiError>> {
+        match *self {
+            TrackEventKind::Meta { kind, data } if is_text_meta_event(kind) => {
+                Some(core::str::from_utf8(data).map(Cow::Borrowed).map_err(|_| {
+                    MidiError::ParseError(ParseError::InvalidEventBytes(alloc::format!(
+                        "Meta event {:02X} is not valid UTF-8",
+                        kind
+                    )))
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The meta-event type bytes whose payload is VLQ-length-prefixed text: Text, Copyright Notice,
+/// Track Name, Instrument Name, Lyric, Marker, and Cue Point.
+fn is_text_meta_event(kind: u8) -> bool {
+    matches!(kind, 0x01..=0x07)
+}
+
+/// A single borrowed track event together with the exact source bytes it was decoded from.
+///
+/// `raw_bytes` covers the event's own bytes (running status aside, this starts at the status byte
+/// and ends after the last data/payload byte) but never the delta-time VLQ that precedes it, so
+/// that a caller rewriting a track can reuse it verbatim regardless of how it chooses to
+/// re-encode the delta time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorrowedTrackEvent<'a> {
+    pub delta_time: U28,
+    pub kind: TrackEventKind<'a>,
+    raw_bytes: &'a [u8],
+}
+
+impl<'a> BorrowedTrackEvent<'a> {
+    /// The exact source slice this event was decoded from, suitable for writing back out
+    /// byte-for-byte instead of re-serializing the decoded `kind`.
+    pub fn raw_bytes(&self) -> &'a [u8] {
+        self.raw_bytes
+    }
+}
+
+/// Read a single track event off the front of `raw`, advancing the slice past the delta-time and
+/// event bytes consumed, and threading running status the same way a full track stream would.
+///
+/// ### Arguments
+/// * `raw` The remaining bytes of the track, starting at a delta-time VLQ
+/// * `running_status` The most recently seen channel-voice status byte, updated in place
+///
+/// ### Returns
+/// The event's delta time, its borrowed `TrackEventKind`, and the raw bytes it was parsed from
+pub fn read_borrowed<'a>(
+    raw: &mut &'a [u8],
+    running_status: &mut Option<u8>,
+) -> Result<BorrowedTrackEvent<'a>, MidiError> {
+    let delta_time = U28::read(raw)?;
+
+    // `raw` at this point is positioned at the start of the event proper (after the delta-time
+    // VLQ but before any running-status elision), so its length delta after parsing the event
+    // gives us the exact byte range to hand back via `raw_bytes`.
+    let event_start = *raw;
+
+    let next_byte = *raw.first().ok_or_else(unexpected_eof)?;
+    let status = if next_byte & 0x80 != 0 {
+        *raw = &raw[1..];
+        next_byte
+    } else {
+        running_status.ok_or_else(|| {
+            MidiError::ParseError(ParseError::InvalidEventBytes(
+                "Data byte seen with no running status in effect".to_string(),
+            ))
+        })?
+    };
+
+    let kind = match status {
+        0xFF => {
+            let meta_type = take_byte(raw)?;
+            let length = U28::read(raw)?.as_u32() as usize;
+            let data = take_slice(raw, length)?;
+            *running_status = None;
+            TrackEventKind::Meta { kind: meta_type, data }
+        }
+        0xF0 | 0xF7 => {
+            let length = U28::read(raw)?.as_u32() as usize;
+            let data = take_slice(raw, length)?;
+            *running_status = None;
+            TrackEventKind::SysEx(data)
+        }
+        0x80..=0xEF => {
+            *running_status = Some(status);
+            let channel = status & 0x0F;
+
+            match status & 0xF0 {
+                0x80 => TrackEventKind::NoteOff {
+                    channel,
+                    note: U7::new(take_byte(raw)?)?,
+                    velocity: U7::new(take_byte(raw)?)?,
+                },
+                0x90 => TrackEventKind::NoteOn {
+                    channel,
+                    note: U7::new(take_byte(raw)?)?,
+                    velocity: U7::new(take_byte(raw)?)?,
+                },
+                0xA0 => TrackEventKind::PolyphonicKeyPressure {
+                    channel,
+                    note: U7::new(take_byte(raw)?)?,
+                    pressure: U7::new(take_byte(raw)?)?,
+                },
+                0xB0 => TrackEventKind::ControlChange {
+                    channel,
+                    controller: U7::new(take_byte(raw)?)?,
+                    value: U7::new(take_byte(raw)?)?,
+                },
+                0xC0 => TrackEventKind::ProgramChange {
+                    channel,
+                    program: U7::new(take_byte(raw)?)?,
+                },
+                0xD0 => TrackEventKind::ChannelPressure {
+                    channel,
+                    pressure: U7::new(take_byte(raw)?)?,
+                },
+                0xE0 => {
+                    let lsb = take_byte(raw)?;
+                    let msb = take_byte(raw)?;
+                    TrackEventKind::PitchBendChange {
+                        channel,
+                        value: U14::from_parts(lsb, msb)?,
+                    }
+                }
+                _ => return Err(unsupported_status(status)),
+            }
+        }
+        _ => return Err(unsupported_status(status)),
+    };
+
+    let consumed = event_start.len() - raw.len();
+    let raw_bytes = &event_start[..consumed];
+
+    Ok(BorrowedTrackEvent {
+        delta_time,
+        kind,
+        raw_bytes,
+    })
+}
+
+fn take_byte(raw: &mut &[u8]) -> Result<u8, MidiError> {
+    let byte = *raw.first().ok_or_else(unexpected_eof)?;
+    *raw = &raw[1..];
+    Ok(byte)
+}
+
+fn take_slice<'a>(raw: &mut &'a [u8], length: usize) -> Result<&'a [u8], MidiError> {
+    if raw.len() < length {
+        return Err(unexpected_eof());
+    }
+    let (data, rest) = raw.split_at(length);
+    *raw = rest;
+    Ok(data)
+}
+
+fn unexpected_eof() -> MidiError {
+    MidiError::ParseError(ParseError::InvalidEventBytes(
+        "Unexpected end of track data".to_string(),
+    ))
+}
+
+fn unsupported_status(status: u8) -> MidiError {
+    MidiError::ParseError(ParseError::NotImplemented(format!(
+        "{:02X} is not a supported track event status byte",
+        status
+    )))
+}
+
+fn range_error(type_name: &str, value: u32) -> MidiError {
+    MidiError::ParseError(ParseError::InvalidEventBytes(format!(
+        "{} does not fit in a {}",
+        value, type_name
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_borrowed_note_on() {
+        let data = [0x00, 0x90, 60, 100];
+        let mut raw = &data[..];
+        let mut running_status = None;
+
+        let event = read_borrowed(&mut raw, &mut running_status).unwrap();
+
+        assert_eq!(event.delta_time.as_u32(), 0);
+        assert_eq!(
+            event.kind,
+            TrackEventKind::NoteOn {
+                channel: 0,
+                note: U7::new(60).unwrap(),
+                velocity: U7::new(100).unwrap(),
+            }
+        );
+        assert_eq!(event.raw_bytes(), &[0x90, 60, 100]);
+        assert_eq!(running_status, Some(0x90));
+        assert!(raw.is_empty());
+    }
+
+    #[test]
+    fn test_read_borrowed_running_status() {
+        let data = [0x00, 62, 0];
+        let mut raw = &data[..];
+        let mut running_status = Some(0x90);
+
+        let event = read_borrowed(&mut raw, &mut running_status).unwrap();
+
+        assert_eq!(
+            event.kind,
+            TrackEventKind::NoteOn {
+                channel: 0,
+                note: U7::new(62).unwrap(),
+                velocity: U7::new(0).unwrap(),
+            }
+        );
+        // No status byte appears in the stream under running status, so raw_bytes only covers
+        // the data bytes actually present.
+        assert_eq!(event.raw_bytes(), &[62, 0]);
+    }
+
+    #[test]
+    fn test_read_borrowed_meta_clears_running_status() {
+        let data = [0x00, 0xFF, 0x2F, 0x00];
+        let mut raw = &data[..];
+        let mut running_status = Some(0x90);
+
+        let event = read_borrowed(&mut raw, &mut running_status).unwrap();
+
+        assert_eq!(event.kind, TrackEventKind::Meta { kind: 0x2F, data: &[] });
+        assert_eq!(event.raw_bytes(), &[0xFF, 0x2F, 0x00]);
+        assert_eq!(running_status, None);
+    }
+
+    #[test]
+    fn test_u28_read_multi_byte_vlq() {
+        // 0x81 0x48 encodes 200 (0x81 contributes the high bits, 0x48 the low 7 bits)
+        let data = [0x81, 0x48];
+        let mut raw = &data[..];
+
+        let value = U28::read(&mut raw).unwrap();
+        assert_eq!(value.as_u32(), 200);
+        assert!(raw.is_empty());
+    }
+
+    #[test]
+    fn test_u7_rejects_out_of_range() {
+        assert!(U7::new(128).is_err());
+        assert!(U7::new(127).is_ok());
+    }
+
+    #[test]
+    fn test_as_text_borrows_track_name_without_copying() {
+        let data = [0x00, 0xFF, 0x03, 0x05, b'P', b'i', b'a', b'n', b'o'];
+        let mut raw = &data[..];
+        let mut running_status = None;
+
+        let event = read_borrowed(&mut raw, &mut running_status).unwrap();
+
+        let text = event.kind.as_text().unwrap().unwrap();
+        assert_eq!(text, "Piano");
+        assert!(matches!(text, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_sysex_to_owned_event_round_trips_payload() {
+        let data = [0x00, 0xF0, 0x03, 0x41, 0x10, 0xF7];
+        let mut raw = &data[..];
+        let mut running_status = None;
+
+        let event = read_borrowed(&mut raw, &mut running_status).unwrap();
+        assert_eq!(event.kind, TrackEventKind::SysEx(&[0x41, 0x10, 0xF7]));
+
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        let owned = event.kind.to_owned_event(0, division, 120).unwrap();
+        assert_eq!(owned.to_bytes().unwrap(), &data[1..]);
+    }
+
+    #[test]
+    fn test_as_text_none_for_non_text_meta_event() {
+        let data = [0x00, 0xFF, 0x2F, 0x00];
+        let mut raw = &data[..];
+        let mut running_status = None;
+
+        let event = read_borrowed(&mut raw, &mut running_status).unwrap();
+
+        assert!(event.kind.as_text().is_none());
+    }
+}
@@ -1,26 +1,27 @@
-use std::{
-    any::Any,
-    fmt::Debug,
-    time::{Duration, Instant},
-};
+use core::{any::Any, fmt::Debug, time::Duration};
+
+#[cfg(feature = "std")]
+use std::time::Instant;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+use alloc::{string::String, vec::Vec};
 
-use crate::{metadata::TimeDivision, midi_error::MidiError};
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
 
-#[cfg(feature = "json")]
 use crate::{
     messages::{
         ChannelPressureEvent, ControlChangeEvent, PitchBendChangeEvent, PolyphonicKeyPressureEvent,
         ProgramChangeEvent, NoteOffEvent, NoteOnEvent
     },
+    metadata::TimeDivision,
     metaevents::{
         CopyRightNoticeEvent, CuePointEvent, EndOfTrack, InstrumentNameEvent, KeySignatureEvent,
         LyricEvent, MarkerEvent, MidiChannelPrefixEvent, MidiPortEvent, SMPTEOffsetEvent,
         SequenceNumber, SequencerSpecificEvent, SetTempoEvent, TextEvent, TimeSignatureEvent,
         TrackNameEvent,
     },
+    midi_error::{MidiError, ParseError},
+    system_exclusive::SystemExclusiveEvent,
 };
 
 pub trait Event: Debug + Any {
@@ -59,6 +60,19 @@ pub trait Event: Debug + Any {
     /// Returns the given delta time
     fn get_delta_time(&self) -> u32;
 
+    /// Overwrites this event's delta time in place, recomputing [`Event::get_time_duration`] to
+    /// match.
+    ///
+    /// Used when events are rearranged relative to each other — e.g. merging multiple tracks'
+    /// events into absolute-tick order and re-splitting them back into tracks — where an event
+    /// keeps its identity but needs a new delta time relative to whatever now precedes it.
+    ///
+    /// # Arguments
+    /// * `delta_time` The new delta time to store
+    /// * `time_division` The file's time division, needed to recompute `time_duration`
+    /// * `tempo` The tempo in effect for this event, needed to recompute `time_duration`
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32);
+
     /// Gets the duration of the event
     ///
     /// Returns an instance of [std::time::Duration]
@@ -67,6 +81,7 @@ pub trait Event: Debug + Any {
     /// Gets the time the event is ocurring relative to the beginning of the track
     ///
     /// Returns an instance of [std::time::Instant]
+    #[cfg(feature = "std")]
     fn get_current_time(&self) -> Instant;
 
     /// Converts the event into bytes that can be stored in a MIDI file
@@ -81,6 +96,19 @@ pub trait Event: Debug + Any {
     /// Returns the bytes with delta time
     fn to_bytes_delta_time(&self) -> Result<Vec<u8>, MidiError>;
 
+    /// Writes this event's delta-time-prefixed bytes directly to `w`, rather than allocating a
+    /// `Vec` and copying it into the sink.
+    ///
+    /// The default just forwards to [`Event::to_bytes_delta_time`]; event types whose encoding
+    /// carries a separately length-prefixed payload (e.g. `SequencerSpecificEvent`,
+    /// `SystemExclusiveEvent`) override this to write their payload straight through instead of
+    /// assembling it in a temporary buffer first.
+    #[cfg(feature = "std")]
+    fn write_to(&self, w: &mut dyn std::io::Write) -> Result<(), MidiError> {
+        w.write_all(&self.to_bytes_delta_time()?)?;
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn Any;
 }
 
@@ -95,89 +123,424 @@ pub trait FromBytes {
     ) -> Result<Self::Output, MidiError>;
 }
 
-pub(crate) fn dispatch_from_bytes<E>(
-    data: &[u8],
-    delta_time: u32,
-    time_division: TimeDivision,
-    tempo: u32,
-) -> Result<Box<dyn Event>, MidiError>
-where
-    E: FromBytes + Event,
-{
-    E::from_bytes(data, delta_time, time_division, tempo)
-        .map(|event| Box::new(event) as Box<dyn Event>)
+/// Checks that `data` has at least `needed` bytes before a `FromBytes` impl reads them, so a
+/// truncated file produces a `ParseError::UnexpectedEof` instead of a panic.
+///
+/// # Arguments
+/// * `data` The bytes a `FromBytes` impl is about to read from
+/// * `needed` The number of bytes the caller is about to access
+/// * `event` The event name, used to build a useful error message
+pub(crate) fn require_len(data: &[u8], needed: usize, event: &str) -> Result<(), MidiError> {
+    if data.len() < needed {
+        return Err(MidiError::ParseError(ParseError::UnexpectedEof {
+            event: String::from(event),
+            needed,
+            got: data.len(),
+        }));
+    }
+
+    Ok(())
+}
+
+/// The concrete kind of a parsed track event, with one variant per event type this crate
+/// understands.
+///
+/// This replaces matching on a `Box<dyn Event>` via `as_any().downcast_ref::<T>()`: that chain
+/// had to fall back to `unimplemented!()` for any type it didn't special-case, and adding a new
+/// event meant remembering to extend every downcast chain by hand. Matching on `EventKind`
+/// instead is exhaustive, so the compiler rejects a build that forgets a variant.
+///
+/// `#[serde(untagged)]` keeps the serialized shape identical to serializing the wrapped event
+/// directly, matching what the old downcast-based `Serialize` impl produced. This relies on the
+/// target format being self-describing (JSON, MessagePack) to pick the right variant back out on
+/// deserialize.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "use_serde", serde(untagged))]
+pub enum EventKind {
+    NoteOn(NoteOnEvent),
+    NoteOff(NoteOffEvent),
+    PolyphonicKeyPressure(PolyphonicKeyPressureEvent),
+    ControlChange(ControlChangeEvent),
+    ProgramChange(ProgramChangeEvent),
+    ChannelPressure(ChannelPressureEvent),
+    PitchBendChange(PitchBendChangeEvent),
+    CopyRightNotice(CopyRightNoticeEvent),
+    CuePoint(CuePointEvent),
+    EndOfTrack(EndOfTrack),
+    InstrumentName(InstrumentNameEvent),
+    KeySignature(KeySignatureEvent),
+    Lyric(LyricEvent),
+    Marker(MarkerEvent),
+    MidiChannelPrefix(MidiChannelPrefixEvent),
+    MidiPort(MidiPortEvent),
+    SequenceNumber(SequenceNumber),
+    SequencerSpecific(SequencerSpecificEvent),
+    SetTempo(SetTempoEvent),
+    SMPTEOffset(SMPTEOffsetEvent),
+    SystemExclusive(SystemExclusiveEvent),
+    Text(TextEvent),
+    TimeSignature(TimeSignatureEvent),
+    TrackName(TrackNameEvent),
 }
 
-#[derive(Debug)]
-pub struct SerializableEvent(pub Box<dyn Event>);
-
-#[cfg(feature = "json")]
-impl Serialize for SerializableEvent {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        if let Some(note_on) = self.0.as_any().downcast_ref::<NoteOnEvent>() {
-            NoteOnEvent::serialize(note_on, serializer)
-        } else if let Some(note_off) = self.0.as_any().downcast_ref::<NoteOffEvent>() {
-            NoteOffEvent::serialize(note_off, serializer)
-        } else if let Some(channel_pressure) =
-            self.0.as_any().downcast_ref::<ChannelPressureEvent>()
-        {
-            ChannelPressureEvent::serialize(channel_pressure, serializer)
-        } else if let Some(control_change) = self.0.as_any().downcast_ref::<ControlChangeEvent>() {
-            ControlChangeEvent::serialize(control_change, serializer)
-        } else if let Some(pitch_bend_change) =
-            self.0.as_any().downcast_ref::<PitchBendChangeEvent>()
-        {
-            PitchBendChangeEvent::serialize(pitch_bend_change, serializer)
-        } else if let Some(polyphonic_key_pressure) =
-            self.0.as_any().downcast_ref::<PolyphonicKeyPressureEvent>()
-        {
-            PolyphonicKeyPressureEvent::serialize(polyphonic_key_pressure, serializer)
-        } else if let Some(program_change) = self.0.as_any().downcast_ref::<ProgramChangeEvent>() {
-            ProgramChangeEvent::serialize(program_change, serializer)
-        } else if let Some(copyright_notice) =
-            self.0.as_any().downcast_ref::<CopyRightNoticeEvent>()
-        {
-            CopyRightNoticeEvent::serialize(copyright_notice, serializer)
-        } else if let Some(cue_point) = self.0.as_any().downcast_ref::<CuePointEvent>() {
-            CuePointEvent::serialize(cue_point, serializer)
-        } else if let Some(end_of_track) = self.0.as_any().downcast_ref::<EndOfTrack>() {
-            EndOfTrack::serialize(end_of_track, serializer)
-        } else if let Some(instrument_name) = self.0.as_any().downcast_ref::<InstrumentNameEvent>()
-        {
-            InstrumentNameEvent::serialize(instrument_name, serializer)
-        } else if let Some(key_signature) = self.0.as_any().downcast_ref::<KeySignatureEvent>() {
-            KeySignatureEvent::serialize(key_signature, serializer)
-        } else if let Some(lyric) = self.0.as_any().downcast_ref::<LyricEvent>() {
-            LyricEvent::serialize(lyric, serializer)
-        } else if let Some(marker) = self.0.as_any().downcast_ref::<MarkerEvent>() {
-            MarkerEvent::serialize(marker, serializer)
-        } else if let Some(midi_channel_prefix) =
-            self.0.as_any().downcast_ref::<MidiChannelPrefixEvent>()
-        {
-            MidiChannelPrefixEvent::serialize(midi_channel_prefix, serializer)
-        } else if let Some(midi_port) = self.0.as_any().downcast_ref::<MidiPortEvent>() {
-            MidiPortEvent::serialize(midi_port, serializer)
-        } else if let Some(sequence_number) = self.0.as_any().downcast_ref::<SequenceNumber>() {
-            SequenceNumber::serialize(sequence_number, serializer)
-        } else if let Some(sequencer_specific) =
-            self.0.as_any().downcast_ref::<SequencerSpecificEvent>()
-        {
-            SequencerSpecificEvent::serialize(sequencer_specific, serializer)
-        } else if let Some(set_tempo) = self.0.as_any().downcast_ref::<SetTempoEvent>() {
-            SetTempoEvent::serialize(set_tempo, serializer)
-        } else if let Some(smpte_offset) = self.0.as_any().downcast_ref::<SMPTEOffsetEvent>() {
-            SMPTEOffsetEvent::serialize(smpte_offset, serializer)
-        } else if let Some(text) = self.0.as_any().downcast_ref::<TextEvent>() {
-            TextEvent::serialize(text, serializer)
-        } else if let Some(time_signature) = self.0.as_any().downcast_ref::<TimeSignatureEvent>() {
-            TimeSignatureEvent::serialize(time_signature, serializer)
-        } else if let Some(track_name) = self.0.as_any().downcast_ref::<TrackNameEvent>() {
-            TrackNameEvent::serialize(track_name, serializer)
-        } else {
-            unimplemented!()
+impl Event for EventKind {
+    fn get_event_name(&self) -> String {
+        match self {
+            EventKind::NoteOn(event) => event.get_event_name(),
+            EventKind::NoteOff(event) => event.get_event_name(),
+            EventKind::PolyphonicKeyPressure(event) => event.get_event_name(),
+            EventKind::ControlChange(event) => event.get_event_name(),
+            EventKind::ProgramChange(event) => event.get_event_name(),
+            EventKind::ChannelPressure(event) => event.get_event_name(),
+            EventKind::PitchBendChange(event) => event.get_event_name(),
+            EventKind::CopyRightNotice(event) => event.get_event_name(),
+            EventKind::CuePoint(event) => event.get_event_name(),
+            EventKind::EndOfTrack(event) => event.get_event_name(),
+            EventKind::InstrumentName(event) => event.get_event_name(),
+            EventKind::KeySignature(event) => event.get_event_name(),
+            EventKind::Lyric(event) => event.get_event_name(),
+            EventKind::Marker(event) => event.get_event_name(),
+            EventKind::MidiChannelPrefix(event) => event.get_event_name(),
+            EventKind::MidiPort(event) => event.get_event_name(),
+            EventKind::SequenceNumber(event) => event.get_event_name(),
+            EventKind::SequencerSpecific(event) => event.get_event_name(),
+            EventKind::SetTempo(event) => event.get_event_name(),
+            EventKind::SMPTEOffset(event) => event.get_event_name(),
+            EventKind::SystemExclusive(event) => event.get_event_name(),
+            EventKind::Text(event) => event.get_event_name(),
+            EventKind::TimeSignature(event) => event.get_event_name(),
+            EventKind::TrackName(event) => event.get_event_name(),
+        }
+    }
+
+    fn is_running_status_allowed(&self) -> bool {
+        match self {
+            EventKind::NoteOn(event) => event.is_running_status_allowed(),
+            EventKind::NoteOff(event) => event.is_running_status_allowed(),
+            EventKind::PolyphonicKeyPressure(event) => event.is_running_status_allowed(),
+            EventKind::ControlChange(event) => event.is_running_status_allowed(),
+            EventKind::ProgramChange(event) => event.is_running_status_allowed(),
+            EventKind::ChannelPressure(event) => event.is_running_status_allowed(),
+            EventKind::PitchBendChange(event) => event.is_running_status_allowed(),
+            EventKind::CopyRightNotice(event) => event.is_running_status_allowed(),
+            EventKind::CuePoint(event) => event.is_running_status_allowed(),
+            EventKind::EndOfTrack(event) => event.is_running_status_allowed(),
+            EventKind::InstrumentName(event) => event.is_running_status_allowed(),
+            EventKind::KeySignature(event) => event.is_running_status_allowed(),
+            EventKind::Lyric(event) => event.is_running_status_allowed(),
+            EventKind::Marker(event) => event.is_running_status_allowed(),
+            EventKind::MidiChannelPrefix(event) => event.is_running_status_allowed(),
+            EventKind::MidiPort(event) => event.is_running_status_allowed(),
+            EventKind::SequenceNumber(event) => event.is_running_status_allowed(),
+            EventKind::SequencerSpecific(event) => event.is_running_status_allowed(),
+            EventKind::SetTempo(event) => event.is_running_status_allowed(),
+            EventKind::SMPTEOffset(event) => event.is_running_status_allowed(),
+            EventKind::SystemExclusive(event) => event.is_running_status_allowed(),
+            EventKind::Text(event) => event.is_running_status_allowed(),
+            EventKind::TimeSignature(event) => event.is_running_status_allowed(),
+            EventKind::TrackName(event) => event.is_running_status_allowed(),
+        }
+    }
+
+    fn event_type(&self) -> u8 {
+        match self {
+            EventKind::NoteOn(event) => event.event_type(),
+            EventKind::NoteOff(event) => event.event_type(),
+            EventKind::PolyphonicKeyPressure(event) => event.event_type(),
+            EventKind::ControlChange(event) => event.event_type(),
+            EventKind::ProgramChange(event) => event.event_type(),
+            EventKind::ChannelPressure(event) => event.event_type(),
+            EventKind::PitchBendChange(event) => event.event_type(),
+            EventKind::CopyRightNotice(event) => event.event_type(),
+            EventKind::CuePoint(event) => event.event_type(),
+            EventKind::EndOfTrack(event) => event.event_type(),
+            EventKind::InstrumentName(event) => event.event_type(),
+            EventKind::KeySignature(event) => event.event_type(),
+            EventKind::Lyric(event) => event.event_type(),
+            EventKind::Marker(event) => event.event_type(),
+            EventKind::MidiChannelPrefix(event) => event.event_type(),
+            EventKind::MidiPort(event) => event.event_type(),
+            EventKind::SequenceNumber(event) => event.event_type(),
+            EventKind::SequencerSpecific(event) => event.event_type(),
+            EventKind::SetTempo(event) => event.event_type(),
+            EventKind::SMPTEOffset(event) => event.event_type(),
+            EventKind::SystemExclusive(event) => event.event_type(),
+            EventKind::Text(event) => event.event_type(),
+            EventKind::TimeSignature(event) => event.event_type(),
+            EventKind::TrackName(event) => event.event_type(),
+        }
+    }
+
+    fn get_channel(&self) -> u8 {
+        match self {
+            EventKind::NoteOn(event) => event.get_channel(),
+            EventKind::NoteOff(event) => event.get_channel(),
+            EventKind::PolyphonicKeyPressure(event) => event.get_channel(),
+            EventKind::ControlChange(event) => event.get_channel(),
+            EventKind::ProgramChange(event) => event.get_channel(),
+            EventKind::ChannelPressure(event) => event.get_channel(),
+            EventKind::PitchBendChange(event) => event.get_channel(),
+            EventKind::CopyRightNotice(event) => event.get_channel(),
+            EventKind::CuePoint(event) => event.get_channel(),
+            EventKind::EndOfTrack(event) => event.get_channel(),
+            EventKind::InstrumentName(event) => event.get_channel(),
+            EventKind::KeySignature(event) => event.get_channel(),
+            EventKind::Lyric(event) => event.get_channel(),
+            EventKind::Marker(event) => event.get_channel(),
+            EventKind::MidiChannelPrefix(event) => event.get_channel(),
+            EventKind::MidiPort(event) => event.get_channel(),
+            EventKind::SequenceNumber(event) => event.get_channel(),
+            EventKind::SequencerSpecific(event) => event.get_channel(),
+            EventKind::SetTempo(event) => event.get_channel(),
+            EventKind::SMPTEOffset(event) => event.get_channel(),
+            EventKind::SystemExclusive(event) => event.get_channel(),
+            EventKind::Text(event) => event.get_channel(),
+            EventKind::TimeSignature(event) => event.get_channel(),
+            EventKind::TrackName(event) => event.get_channel(),
+        }
+    }
+
+    fn get_event_size(&self) -> u8 {
+        match self {
+            EventKind::NoteOn(event) => event.get_event_size(),
+            EventKind::NoteOff(event) => event.get_event_size(),
+            EventKind::PolyphonicKeyPressure(event) => event.get_event_size(),
+            EventKind::ControlChange(event) => event.get_event_size(),
+            EventKind::ProgramChange(event) => event.get_event_size(),
+            EventKind::ChannelPressure(event) => event.get_event_size(),
+            EventKind::PitchBendChange(event) => event.get_event_size(),
+            EventKind::CopyRightNotice(event) => event.get_event_size(),
+            EventKind::CuePoint(event) => event.get_event_size(),
+            EventKind::EndOfTrack(event) => event.get_event_size(),
+            EventKind::InstrumentName(event) => event.get_event_size(),
+            EventKind::KeySignature(event) => event.get_event_size(),
+            EventKind::Lyric(event) => event.get_event_size(),
+            EventKind::Marker(event) => event.get_event_size(),
+            EventKind::MidiChannelPrefix(event) => event.get_event_size(),
+            EventKind::MidiPort(event) => event.get_event_size(),
+            EventKind::SequenceNumber(event) => event.get_event_size(),
+            EventKind::SequencerSpecific(event) => event.get_event_size(),
+            EventKind::SetTempo(event) => event.get_event_size(),
+            EventKind::SMPTEOffset(event) => event.get_event_size(),
+            EventKind::SystemExclusive(event) => event.get_event_size(),
+            EventKind::Text(event) => event.get_event_size(),
+            EventKind::TimeSignature(event) => event.get_event_size(),
+            EventKind::TrackName(event) => event.get_event_size(),
         }
     }
+
+    fn get_delta_time(&self) -> u32 {
+        match self {
+            EventKind::NoteOn(event) => event.get_delta_time(),
+            EventKind::NoteOff(event) => event.get_delta_time(),
+            EventKind::PolyphonicKeyPressure(event) => event.get_delta_time(),
+            EventKind::ControlChange(event) => event.get_delta_time(),
+            EventKind::ProgramChange(event) => event.get_delta_time(),
+            EventKind::ChannelPressure(event) => event.get_delta_time(),
+            EventKind::PitchBendChange(event) => event.get_delta_time(),
+            EventKind::CopyRightNotice(event) => event.get_delta_time(),
+            EventKind::CuePoint(event) => event.get_delta_time(),
+            EventKind::EndOfTrack(event) => event.get_delta_time(),
+            EventKind::InstrumentName(event) => event.get_delta_time(),
+            EventKind::KeySignature(event) => event.get_delta_time(),
+            EventKind::Lyric(event) => event.get_delta_time(),
+            EventKind::Marker(event) => event.get_delta_time(),
+            EventKind::MidiChannelPrefix(event) => event.get_delta_time(),
+            EventKind::MidiPort(event) => event.get_delta_time(),
+            EventKind::SequenceNumber(event) => event.get_delta_time(),
+            EventKind::SequencerSpecific(event) => event.get_delta_time(),
+            EventKind::SetTempo(event) => event.get_delta_time(),
+            EventKind::SMPTEOffset(event) => event.get_delta_time(),
+            EventKind::SystemExclusive(event) => event.get_delta_time(),
+            EventKind::Text(event) => event.get_delta_time(),
+            EventKind::TimeSignature(event) => event.get_delta_time(),
+            EventKind::TrackName(event) => event.get_delta_time(),
+        }
+    }
+
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        match self {
+            EventKind::NoteOn(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::NoteOff(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::PolyphonicKeyPressure(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::ControlChange(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::ProgramChange(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::ChannelPressure(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::PitchBendChange(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::CopyRightNotice(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::CuePoint(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::EndOfTrack(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::InstrumentName(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::KeySignature(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::Lyric(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::Marker(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::MidiChannelPrefix(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::MidiPort(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::SequenceNumber(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::SequencerSpecific(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::SetTempo(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::SMPTEOffset(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::SystemExclusive(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::Text(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::TimeSignature(event) => event.set_delta_time(delta_time, time_division, tempo),
+            EventKind::TrackName(event) => event.set_delta_time(delta_time, time_division, tempo),
+        }
+    }
+
+    fn get_time_duration(&self) -> Duration {
+        match self {
+            EventKind::NoteOn(event) => event.get_time_duration(),
+            EventKind::NoteOff(event) => event.get_time_duration(),
+            EventKind::PolyphonicKeyPressure(event) => event.get_time_duration(),
+            EventKind::ControlChange(event) => event.get_time_duration(),
+            EventKind::ProgramChange(event) => event.get_time_duration(),
+            EventKind::ChannelPressure(event) => event.get_time_duration(),
+            EventKind::PitchBendChange(event) => event.get_time_duration(),
+            EventKind::CopyRightNotice(event) => event.get_time_duration(),
+            EventKind::CuePoint(event) => event.get_time_duration(),
+            EventKind::EndOfTrack(event) => event.get_time_duration(),
+            EventKind::InstrumentName(event) => event.get_time_duration(),
+            EventKind::KeySignature(event) => event.get_time_duration(),
+            EventKind::Lyric(event) => event.get_time_duration(),
+            EventKind::Marker(event) => event.get_time_duration(),
+            EventKind::MidiChannelPrefix(event) => event.get_time_duration(),
+            EventKind::MidiPort(event) => event.get_time_duration(),
+            EventKind::SequenceNumber(event) => event.get_time_duration(),
+            EventKind::SequencerSpecific(event) => event.get_time_duration(),
+            EventKind::SetTempo(event) => event.get_time_duration(),
+            EventKind::SMPTEOffset(event) => event.get_time_duration(),
+            EventKind::SystemExclusive(event) => event.get_time_duration(),
+            EventKind::Text(event) => event.get_time_duration(),
+            EventKind::TimeSignature(event) => event.get_time_duration(),
+            EventKind::TrackName(event) => event.get_time_duration(),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn get_current_time(&self) -> Instant {
+        match self {
+            EventKind::NoteOn(event) => event.get_current_time(),
+            EventKind::NoteOff(event) => event.get_current_time(),
+            EventKind::PolyphonicKeyPressure(event) => event.get_current_time(),
+            EventKind::ControlChange(event) => event.get_current_time(),
+            EventKind::ProgramChange(event) => event.get_current_time(),
+            EventKind::ChannelPressure(event) => event.get_current_time(),
+            EventKind::PitchBendChange(event) => event.get_current_time(),
+            EventKind::CopyRightNotice(event) => event.get_current_time(),
+            EventKind::CuePoint(event) => event.get_current_time(),
+            EventKind::EndOfTrack(event) => event.get_current_time(),
+            EventKind::InstrumentName(event) => event.get_current_time(),
+            EventKind::KeySignature(event) => event.get_current_time(),
+            EventKind::Lyric(event) => event.get_current_time(),
+            EventKind::Marker(event) => event.get_current_time(),
+            EventKind::MidiChannelPrefix(event) => event.get_current_time(),
+            EventKind::MidiPort(event) => event.get_current_time(),
+            EventKind::SequenceNumber(event) => event.get_current_time(),
+            EventKind::SequencerSpecific(event) => event.get_current_time(),
+            EventKind::SetTempo(event) => event.get_current_time(),
+            EventKind::SMPTEOffset(event) => event.get_current_time(),
+            EventKind::SystemExclusive(event) => event.get_current_time(),
+            EventKind::Text(event) => event.get_current_time(),
+            EventKind::TimeSignature(event) => event.get_current_time(),
+            EventKind::TrackName(event) => event.get_current_time(),
+        }
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, MidiError> {
+        match self {
+            EventKind::NoteOn(event) => event.to_bytes(),
+            EventKind::NoteOff(event) => event.to_bytes(),
+            EventKind::PolyphonicKeyPressure(event) => event.to_bytes(),
+            EventKind::ControlChange(event) => event.to_bytes(),
+            EventKind::ProgramChange(event) => event.to_bytes(),
+            EventKind::ChannelPressure(event) => event.to_bytes(),
+            EventKind::PitchBendChange(event) => event.to_bytes(),
+            EventKind::CopyRightNotice(event) => event.to_bytes(),
+            EventKind::CuePoint(event) => event.to_bytes(),
+            EventKind::EndOfTrack(event) => event.to_bytes(),
+            EventKind::InstrumentName(event) => event.to_bytes(),
+            EventKind::KeySignature(event) => event.to_bytes(),
+            EventKind::Lyric(event) => event.to_bytes(),
+            EventKind::Marker(event) => event.to_bytes(),
+            EventKind::MidiChannelPrefix(event) => event.to_bytes(),
+            EventKind::MidiPort(event) => event.to_bytes(),
+            EventKind::SequenceNumber(event) => event.to_bytes(),
+            EventKind::SequencerSpecific(event) => event.to_bytes(),
+            EventKind::SetTempo(event) => event.to_bytes(),
+            EventKind::SMPTEOffset(event) => event.to_bytes(),
+            EventKind::SystemExclusive(event) => event.to_bytes(),
+            EventKind::Text(event) => event.to_bytes(),
+            EventKind::TimeSignature(event) => event.to_bytes(),
+            EventKind::TrackName(event) => event.to_bytes(),
+        }
+    }
+
+    fn to_bytes_delta_time(&self) -> Result<Vec<u8>, MidiError> {
+        match self {
+            EventKind::NoteOn(event) => event.to_bytes_delta_time(),
+            EventKind::NoteOff(event) => event.to_bytes_delta_time(),
+            EventKind::PolyphonicKeyPressure(event) => event.to_bytes_delta_time(),
+            EventKind::ControlChange(event) => event.to_bytes_delta_time(),
+            EventKind::ProgramChange(event) => event.to_bytes_delta_time(),
+            EventKind::ChannelPressure(event) => event.to_bytes_delta_time(),
+            EventKind::PitchBendChange(event) => event.to_bytes_delta_time(),
+            EventKind::CopyRightNotice(event) => event.to_bytes_delta_time(),
+            EventKind::CuePoint(event) => event.to_bytes_delta_time(),
+            EventKind::EndOfTrack(event) => event.to_bytes_delta_time(),
+            EventKind::InstrumentName(event) => event.to_bytes_delta_time(),
+            EventKind::KeySignature(event) => event.to_bytes_delta_time(),
+            EventKind::Lyric(event) => event.to_bytes_delta_time(),
+            EventKind::Marker(event) => event.to_bytes_delta_time(),
+            EventKind::MidiChannelPrefix(event) => event.to_bytes_delta_time(),
+            EventKind::MidiPort(event) => event.to_bytes_delta_time(),
+            EventKind::SequenceNumber(event) => event.to_bytes_delta_time(),
+            EventKind::SequencerSpecific(event) => event.to_bytes_delta_time(),
+            EventKind::SetTempo(event) => event.to_bytes_delta_time(),
+            EventKind::SMPTEOffset(event) => event.to_bytes_delta_time(),
+            EventKind::SystemExclusive(event) => event.to_bytes_delta_time(),
+            EventKind::Text(event) => event.to_bytes_delta_time(),
+            EventKind::TimeSignature(event) => event.to_bytes_delta_time(),
+            EventKind::TrackName(event) => event.to_bytes_delta_time(),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn write_to(&self, w: &mut dyn std::io::Write) -> Result<(), MidiError> {
+        match self {
+            EventKind::NoteOn(event) => event.write_to(w),
+            EventKind::NoteOff(event) => event.write_to(w),
+            EventKind::PolyphonicKeyPressure(event) => event.write_to(w),
+            EventKind::ControlChange(event) => event.write_to(w),
+            EventKind::ProgramChange(event) => event.write_to(w),
+            EventKind::ChannelPressure(event) => event.write_to(w),
+            EventKind::PitchBendChange(event) => event.write_to(w),
+            EventKind::CopyRightNotice(event) => event.write_to(w),
+            EventKind::CuePoint(event) => event.write_to(w),
+            EventKind::EndOfTrack(event) => event.write_to(w),
+            EventKind::InstrumentName(event) => event.write_to(w),
+            EventKind::KeySignature(event) => event.write_to(w),
+            EventKind::Lyric(event) => event.write_to(w),
+            EventKind::Marker(event) => event.write_to(w),
+            EventKind::MidiChannelPrefix(event) => event.write_to(w),
+            EventKind::MidiPort(event) => event.write_to(w),
+            EventKind::SequenceNumber(event) => event.write_to(w),
+            EventKind::SequencerSpecific(event) => event.write_to(w),
+            EventKind::SetTempo(event) => event.write_to(w),
+            EventKind::SMPTEOffset(event) => event.write_to(w),
+            EventKind::SystemExclusive(event) => event.write_to(w),
+            EventKind::Text(event) => event.write_to(w),
+            EventKind::TimeSignature(event) => event.write_to(w),
+            EventKind::TrackName(event) => event.write_to(w),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
+
+/// Wraps a parsed event for serialization/deserialization.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
+pub struct SerializableEvent(pub EventKind);
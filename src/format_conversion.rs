@@ -0,0 +1,134 @@
+//! Structural conversion between SMF format 0 (a single track interleaving every channel) and
+//! format 1 (a conductor track of non-channel-voice events plus one track per channel).
+//!
+//! Both directions work by flattening every source track's events into `(absolute_tick,
+//! EventKind)` pairs, regrouping those pairs, then re-deriving each event's delta time relative
+//! to whatever now precedes it in its new track via [`Event::set_delta_time`].
+
+use alloc::vec::Vec;
+
+use crate::{
+    events::{Event, EventKind, SerializableEvent},
+    metadata::TimeDivision,
+    midi_error::MidiError,
+    track::Track,
+};
+
+/// The number of MIDI channels a format-0 track can interleave, and so the number of per-channel
+/// tracks [`to_format_1`] splits into.
+const CHANNEL_COUNT: u8 = 16;
+
+/// Whether `event` is a channel-voice message, i.e. carries a meaningful channel number and
+/// belongs on one of [`to_format_1`]'s per-channel tracks rather than its conductor track.
+fn is_channel_voice(event: &EventKind) -> bool {
+    matches!(
+        event,
+        EventKind::NoteOn(_)
+            | EventKind::NoteOff(_)
+            | EventKind::PolyphonicKeyPressure(_)
+            | EventKind::ControlChange(_)
+            | EventKind::ProgramChange(_)
+            | EventKind::ChannelPressure(_)
+            | EventKind::PitchBendChange(_)
+    )
+}
+
+/// Re-delta-encode a list of events already in ascending absolute-tick order into a `Track`.
+///
+/// ### Arguments
+/// * `events` Each event paired with its absolute tick position, in ascending order
+/// * `time_division` The file's time division, needed to recompute each event's `time_duration`
+/// * `tempo` The tempo in effect at the start of `events`, used to recompute `time_duration`
+fn build_track(
+    events: Vec<(u32, EventKind)>,
+    time_division: TimeDivision,
+    tempo: u32,
+) -> Track {
+    let mut tempo = tempo;
+    let mut previous_tick = 0u32;
+    let mut track_events = Vec::with_capacity(events.len());
+
+    for (tick, mut event) in events {
+        event.set_delta_time(tick - previous_tick, time_division, tempo);
+        previous_tick = tick;
+
+        if let EventKind::SetTempo(set_tempo) = &event {
+            tempo = set_tempo.tempo.round() as u32;
+        }
+
+        track_events.push(SerializableEvent(event));
+    }
+
+    Track {
+        events: track_events,
+        track_size: 0,
+    }
+}
+
+/// Merge every track in `tracks` into a single format-0 track, interleaving all of their events
+/// in absolute-tick order.
+///
+/// Ties (two events sharing the same absolute tick) keep the relative order of the tracks they
+/// came from, since the merge is a stable sort.
+///
+/// ### Arguments
+/// * `tracks` The format-1 track list to merge (conductor track plus per-channel tracks)
+/// * `time_division` The file's time division, needed to keep `time_duration` in sync
+/// * `tempo` The tempo in effect before the first `SetTempoEvent` is seen
+///
+/// ### Returns
+/// A single track holding every source track's events, in absolute-tick order
+pub fn to_format_0(tracks: &[Track], time_division: TimeDivision, tempo: u32) -> Result<Track, MidiError> {
+    let mut tagged: Vec<(u32, EventKind)> = Vec::new();
+
+    for track in tracks {
+        let mut absolute_tick = 0u32;
+        for event in &track.events {
+            absolute_tick += event.0.get_delta_time();
+            tagged.push((absolute_tick, event.0.clone()));
+        }
+    }
+
+    tagged.sort_by_key(|(tick, _)| *tick);
+
+    Ok(build_track(tagged, time_division, tempo))
+}
+
+/// Split a single format-0 track into a format-1 track list: a conductor track holding every
+/// non-channel-voice event (meta events, SysEx), followed by one track per channel that has at
+/// least one channel-voice event.
+///
+/// ### Arguments
+/// * `track` The format-0 track to split
+/// * `time_division` The file's time division, needed to keep `time_duration` in sync
+/// * `tempo` The tempo in effect before the first `SetTempoEvent` is seen
+///
+/// ### Returns
+/// The conductor track followed by each non-empty channel's track
+pub fn to_format_1(track: &Track, time_division: TimeDivision, tempo: u32) -> Result<Vec<Track>, MidiError> {
+    let mut conductor: Vec<(u32, EventKind)> = Vec::new();
+    let mut by_channel: Vec<Vec<(u32, EventKind)>> = (0..CHANNEL_COUNT).map(|_| Vec::new()).collect();
+
+    let mut absolute_tick = 0u32;
+    for event in &track.events {
+        absolute_tick += event.0.get_delta_time();
+        let event = event.0.clone();
+
+        if is_channel_voice(&event) {
+            by_channel[event.get_channel() as usize].push((absolute_tick, event));
+        } else {
+            conductor.push((absolute_tick, event));
+        }
+    }
+
+    let mut tracks = Vec::with_capacity(1 + CHANNEL_COUNT as usize);
+    tracks.push(build_track(conductor, time_division, tempo));
+
+    for channel_events in by_channel {
+        if !channel_events.is_empty() {
+            tracks.push(build_track(channel_events, time_division, tempo));
+        }
+    }
+
+    Ok(tracks)
+}
@@ -1,16 +1,49 @@
+// This crate builds as `no_std` when the default-on `std` feature is disabled, relying on `alloc`
+// for `String`/`Vec`/`Box`. The `std` feature adds file I/O (`Midi::new`), wall-clock event timestamps
+// (`current_time` on events, the `Sequencer`), and `std::error::Error` impls.
+//
+// `use_serde` is the shared serialization feature: it derives `Serialize`/`Deserialize` on every
+// event and container type, skipping each `current_time: Instant` field and recomputing it with
+// `Instant::now()` on deserialize. `json` and `msgpack` each build on top of it to add one
+// concrete wire format (`serde_json`/`rmp_serde` respectively) without coupling the two formats
+// to each other.
+#![cfg_attr(not(feature = "std"), no_std)]
 #[warn(missing_docs)]
 
-#[cfg(feature = "json")]
+// Always linked, even in std builds, so error/event types can name `alloc::string::FromUtf8Error`
+// etc. without the type differing between configurations.
+extern crate alloc;
+
+#[cfg(feature = "use_serde")]
 extern crate serde;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
+
+#[cfg(feature = "std")]
+use std::{
+    fs::File,
+    io::{BufWriter, Read, Write},
+    path::Path,
+    string::String,
+    vec::Vec,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec::Vec};
 
-use std::{path::Path, fs::File, io::Read};
-use metadata::MetaData;
+#[cfg(feature = "std")]
+use metadata::{FileFormat, MetaData};
+#[cfg(feature = "std")]
 use metaevents::TimeSignature;
+#[cfg(feature = "std")]
 use midi_error::MidiError;
+#[cfg(feature = "std")]
 use track::Track;
+#[cfg(feature = "std")]
+use tempo_map::TempoMap;
+#[cfg(feature = "std")]
+use ordered_events::EventsInOrder;
 
 use hex::encode_upper;
 use log::debug;
@@ -18,13 +51,25 @@ use log::debug;
 pub mod metadata;
 pub mod track;
 pub mod events;
+pub mod format_conversion;
 pub mod metaevents;
 pub mod midi_error;
 pub mod messages;
 pub mod note;
+pub mod live;
+pub mod borrowed;
+pub mod tempo_map;
+#[cfg(feature = "std")]
+pub mod sequencer;
+#[cfg(feature = "std")]
+pub mod ordered_events;
+pub mod rmid;
+pub mod system_exclusive;
+pub mod tick_cursor;
 
+#[cfg(feature = "std")]
 #[derive(Debug)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct Midi {
     pub midi_file: String,
     pub meta_data: MetaData,
@@ -33,6 +78,7 @@ pub struct Midi {
     pub track_list: Vec<Track>,
 }
 
+#[cfg(feature = "std")]
 impl Midi {
     pub fn new(midi_file: &'static str) -> Result<Self, MidiError> {
         let tempo: u32 = 120;
@@ -42,8 +88,17 @@ impl Midi {
         let mut file = File::open(path)?;
         file.read_to_end(&mut file_contents)?;
 
-        let meta_data = MetaData::new(&file_contents[0..14])?;
-        let track_list = Track::get_track_list(&file_contents[14..])?;
+        // Some DAWs and OS media libraries distribute MIDI wrapped in an RMID RIFF container
+        // rather than as a bare SMF byte stream; sniff for that and unwrap it transparently so
+        // `.rmi` files parse the same way `.mid` files do.
+        let file_contents = if rmid::is_rmid(&file_contents) {
+            rmid::unwrap_rmid(&file_contents)?.to_vec()
+        } else {
+            file_contents
+        };
+
+        let meta_data = MetaData::new(&file_contents)?;
+        let track_list = Track::get_track_list(&file_contents[14..], meta_data.time_division)?;
 
         let midi_struct = Self {
             midi_file: String::from(midi_file),
@@ -61,6 +116,138 @@ impl Midi {
         serde_json::to_string_pretty(self)
     }
 
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Serialize this `Midi` to the compact binary MessagePack format, suitable for storage or
+    /// transport where JSON's textual overhead isn't worth it.
+    #[cfg(feature = "msgpack")]
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec(self)
+    }
+
+    /// Reconstruct a `Midi` previously written by [`Midi::to_msgpack`].
+    #[cfg(feature = "msgpack")]
+    pub fn from_msgpack(data: &[u8]) -> Result<Self, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(data)
+    }
+
+    /// Reassemble this `Midi` back into a complete Standard MIDI File byte stream: the `MThd`
+    /// header chunk from `meta_data`, followed by one `MTrk` chunk per track (each one ensuring
+    /// it ends with an `EndOfTrack`, per `Track::to_bytes`).
+    ///
+    /// ### Returns
+    /// The full SMF byte stream, suitable for `Midi::new` to parse back
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MidiError> {
+        let mut bytes = self.meta_data.to_bytes();
+
+        for track in &self.track_list {
+            bytes.extend_from_slice(&track.to_bytes()?);
+        }
+
+        Ok(bytes)
+    }
+
+    /// Same as [`Midi::to_bytes`], but streams the `MThd` header and each `MTrk` chunk straight to
+    /// `w` instead of concatenating the whole file into one owned `Vec` first.
+    ///
+    /// ### Arguments
+    /// * `w` Where to write the encoded bytes
+    pub fn write_to<W: Write>(&self, w: &mut W) -> Result<(), MidiError> {
+        w.write_all(&self.meta_data.to_bytes())?;
+
+        for track in &self.track_list {
+            track.write_to(w)?;
+        }
+
+        Ok(())
+    }
+
+    /// Serialize this `Midi` and write it out to `path` as a `.mid` file.
+    ///
+    /// ### Arguments
+    /// * `path` Where to write the file
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), MidiError> {
+        let file = File::create(path)?;
+        let mut writer = BufWriter::new(file);
+        self.write_to(&mut writer)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Merge every track's events into a single performance-order stream.
+    ///
+    /// By SMF convention the tempo map lives on track 0, so that track's `SetTempoEvent`s are
+    /// used to convert each merged event's absolute tick into a wall-clock `Duration`.
+    ///
+    /// ### Returns
+    /// An iterator yielding every track's events interleaved in absolute-tick order
+    pub fn events_in_order(&self) -> EventsInOrder<'_> {
+        let tempo_map = match self.track_list.first() {
+            Some(track) => TempoMap::new(&track.events, self.meta_data.time_division, self.tempo),
+            None => TempoMap::new(&[], self.meta_data.time_division, self.tempo),
+        };
+
+        EventsInOrder::new(&self.track_list, tempo_map)
+    }
+
+    /// Convert this `Midi` to SMF format 0: every track's events merged into a single
+    /// interleaved track, in absolute-tick order.
+    ///
+    /// A no-op (aside from re-deriving delta times) if this `Midi` is already format 0.
+    ///
+    /// ### Returns
+    /// A new `Midi` holding the merged track, with `meta_data` updated to format 0
+    pub fn to_format_0(&self) -> Result<Self, MidiError> {
+        let merged = format_conversion::to_format_0(&self.track_list, self.meta_data.time_division, self.tempo)?;
+
+        Ok(Self {
+            midi_file: self.midi_file.clone(),
+            meta_data: MetaData {
+                num_of_tracks: 1,
+                file_format: FileFormat::SINGLE_TRACK,
+                time_division: self.meta_data.time_division,
+            },
+            tempo: self.tempo,
+            time_signature: self.time_signature.clone(),
+            track_list: vec![merged],
+        })
+    }
+
+    /// Convert this `Midi` to SMF format 1: a conductor track of non-channel-voice events
+    /// followed by one track per channel that has at least one channel-voice event.
+    ///
+    /// Only meaningful starting from a format-0 `Midi`; if `track_list` holds more than one
+    /// track already, they're merged via [`Midi::to_format_0`] first so the split starts from a
+    /// single interleaved event stream.
+    ///
+    /// ### Returns
+    /// A new `Midi` holding the split tracks, with `meta_data` updated to format 1
+    pub fn to_format_1(&self) -> Result<Self, MidiError> {
+        let single_track = if self.track_list.len() == 1 {
+            self.track_list[0].clone()
+        } else {
+            self.to_format_0()?.track_list.remove(0)
+        };
+
+        let track_list = format_conversion::to_format_1(&single_track, self.meta_data.time_division, self.tempo)?;
+
+        Ok(Self {
+            midi_file: self.midi_file.clone(),
+            meta_data: MetaData {
+                num_of_tracks: track_list.len() as u16,
+                file_format: FileFormat::MULTI_TRACK,
+                time_division: self.meta_data.time_division,
+            },
+            tempo: self.tempo,
+            time_signature: self.time_signature.clone(),
+            track_list,
+        })
+    }
+
 }
 
 pub fn print_file_contents(file_conents: &[u8]) {
@@ -0,0 +1,361 @@
+//! Real-time ("live") MIDI message parsing and encoding.
+//!
+//! The `events` module models Standard MIDI File track events, which are always framed with a
+//! delta-time VLQ and read out of a complete file. This module instead models the wire protocol
+//! used when talking to an actual MIDI port: channel-voice messages with no delta time, System
+//! Common messages, and System Real-Time messages. Channel-voice messages are decoded using the
+//! same structs as the file-event path so callers can share note/controller handling between
+//! playback and live rendering. Program Change (`0xC`) is not decoded: it returns a
+//! `NotImplemented` parse error.
+
+use crate::{
+    events::{Event, FromBytes},
+    messages::{
+        ChannelPressureEvent, ControlChangeEvent, NoteOffEvent, NoteOnEvent,
+        PitchBendChangeEvent, PolyphonicKeyPressureEvent, CHANNEL_MASK, EVENT_MASK,
+    },
+    metadata::TimeDivision,
+    midi_error::{MidiError, ParseError},
+};
+
+/// Live messages carry no delta time, so channel-voice events are decoded with a fixed,
+/// irrelevant time base purely to satisfy the existing `FromBytes`/constructor signatures.
+const LIVE_TIME_DIVISION: TimeDivision = TimeDivision::PulsesPerQuarterNote(96);
+const LIVE_TEMPO: u32 = 120;
+
+/// A System Common message: addressed to all devices on the MIDI bus, but not a transport
+/// real-time byte.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SystemCommon {
+    /// MIDI Time Code quarter-frame (`0xF1`).
+    TimeCodeQuarterFrame(u8),
+    /// Song Position Pointer, in MIDI beats (`0xF2`).
+    SongPositionPointer(u16),
+    /// Song Select (`0xF3`).
+    SongSelect(u8),
+    /// Tune Request (`0xF6`).
+    TuneRequest,
+}
+
+/// A System Real-Time message. These may appear interleaved inside any other message and must
+/// not disturb running status.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SystemRealTime {
+    /// Timing Clock (`0xF8`).
+    Clock,
+    /// Start (`0xFA`).
+    Start,
+    /// Continue (`0xFB`).
+    Continue,
+    /// Stop (`0xFC`).
+    Stop,
+    /// Active Sensing (`0xFE`).
+    ActiveSensing,
+    /// Reset (`0xFF`).
+    Reset,
+}
+
+/// A System Exclusive message: a manufacturer-specific payload bracketed by a leading `0xF0` and
+/// a terminating `0xF7`. The stored bytes are the payload only, excluding both bracketing bytes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemExclusive {
+    pub data: Vec<u8>,
+}
+
+/// A single live MIDI message as it appears on the wire, with no delta-time framing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LiveEvent {
+    NoteOn(NoteOnEvent),
+    NoteOff(NoteOffEvent),
+    PolyphonicKeyPressure(PolyphonicKeyPressureEvent),
+    ControlChange(ControlChangeEvent),
+    ChannelPressure(ChannelPressureEvent),
+    PitchBendChange(PitchBendChangeEvent),
+    Common(SystemCommon),
+    RealTime(SystemRealTime),
+    SysEx(SystemExclusive),
+}
+
+impl LiveEvent {
+    /// Parse a single live MIDI message from the start of `data`.
+    ///
+    /// Unlike the file-event path, live messages are read from a raw streamed byte source (e.g. a
+    /// hardware MIDI input) with no length prefix, so callers need to know how many bytes of
+    /// `data` the parsed message actually consumed in order to advance their read position.
+    ///
+    /// # Arguments
+    /// * `data` The bytes to parse, starting with a status byte
+    ///
+    /// # Returns
+    /// The parsed `LiveEvent` and the number of bytes consumed from `data`
+    pub fn from_bytes(data: &[u8]) -> Result<(Self, usize), MidiError> {
+        if data.is_empty() {
+            return Err(MidiError::ParseError(ParseError::InvalidEventBytes(
+                "No bytes to parse a live event from".to_string(),
+            )));
+        }
+
+        let status = data[0];
+
+        match status {
+            0xF1 => Ok((
+                LiveEvent::Common(SystemCommon::TimeCodeQuarterFrame(
+                    *data.get(1).ok_or_else(too_short)?,
+                )),
+                2,
+            )),
+            0xF2 => {
+                let lsb = *data.get(1).ok_or_else(too_short)? as u16;
+                let msb = *data.get(2).ok_or_else(too_short)? as u16;
+                Ok((
+                    LiveEvent::Common(SystemCommon::SongPositionPointer(lsb | (msb << 7))),
+                    3,
+                ))
+            }
+            0xF3 => Ok((
+                LiveEvent::Common(SystemCommon::SongSelect(
+                    *data.get(1).ok_or_else(too_short)?,
+                )),
+                2,
+            )),
+            0xF6 => Ok((LiveEvent::Common(SystemCommon::TuneRequest), 1)),
+            0xF0 => Self::sysex_from_bytes(data),
+            0xF8 => Ok((LiveEvent::RealTime(SystemRealTime::Clock), 1)),
+            0xFA => Ok((LiveEvent::RealTime(SystemRealTime::Start), 1)),
+            0xFB => Ok((LiveEvent::RealTime(SystemRealTime::Continue), 1)),
+            0xFC => Ok((LiveEvent::RealTime(SystemRealTime::Stop), 1)),
+            0xFE => Ok((LiveEvent::RealTime(SystemRealTime::ActiveSensing), 1)),
+            0xFF => Ok((LiveEvent::RealTime(SystemRealTime::Reset), 1)),
+            0x80..=0xEF => Self::channel_voice_from_bytes(status, data),
+            _ => Err(MidiError::ParseError(ParseError::NotImplemented(format!(
+                "{:02X} is not a supported live MIDI status byte",
+                status
+            )))),
+        }
+    }
+
+    /// Parse a System Exclusive message starting at `data[0] == 0xF0`, scanning for the `0xF7`
+    /// terminator.
+    ///
+    /// # Arguments
+    /// * `data` The bytes to parse, starting with the `0xF0` status byte
+    ///
+    /// # Returns
+    /// The parsed `LiveEvent::SysEx` and the number of bytes consumed, including both the leading
+    /// `0xF0` and the terminating `0xF7`
+    fn sysex_from_bytes(data: &[u8]) -> Result<(Self, usize), MidiError> {
+        let terminator = data[1..]
+            .iter()
+            .position(|&byte| byte == 0xF7)
+            .ok_or_else(|| {
+                MidiError::ParseError(ParseError::InvalidEventBytes(
+                    "System Exclusive message is missing its 0xF7 terminator".to_string(),
+                ))
+            })?;
+
+        let payload = data[1..1 + terminator].to_vec();
+        let consumed = 1 + terminator + 1;
+
+        Ok((LiveEvent::SysEx(SystemExclusive { data: payload }), consumed))
+    }
+
+    fn channel_voice_from_bytes(status: u8, data: &[u8]) -> Result<(Self, usize), MidiError> {
+        let event_type = (status & EVENT_MASK) >> 4;
+        let channel = status & CHANNEL_MASK;
+
+        let event = match event_type {
+            0x9 => LiveEvent::NoteOn(NoteOnEvent::from_bytes(
+                data,
+                0,
+                LIVE_TIME_DIVISION,
+                LIVE_TEMPO,
+            )?),
+            0x8 => LiveEvent::NoteOff(NoteOffEvent::from_bytes(
+                data,
+                0,
+                LIVE_TIME_DIVISION,
+                LIVE_TEMPO,
+            )?),
+            0xA => LiveEvent::PolyphonicKeyPressure(PolyphonicKeyPressureEvent::from_bytes(
+                data,
+                0,
+                LIVE_TIME_DIVISION,
+                LIVE_TEMPO,
+            )?),
+            0xB => LiveEvent::ControlChange(ControlChangeEvent::new_from_status(
+                data.get(1..3).ok_or_else(too_short)?,
+                channel,
+                LIVE_TIME_DIVISION,
+                LIVE_TEMPO,
+            )?),
+            0xD => LiveEvent::ChannelPressure(ChannelPressureEvent::from_bytes(
+                data,
+                0,
+                LIVE_TIME_DIVISION,
+                LIVE_TEMPO,
+            )?),
+            0xE => LiveEvent::PitchBendChange(PitchBendChangeEvent::from_bytes(
+                data,
+                0,
+                LIVE_TIME_DIVISION,
+                LIVE_TEMPO,
+            )?),
+            _ => {
+                return Err(MidiError::ParseError(ParseError::NotImplemented(format!(
+                    "Live event {:02X} is not implemented!",
+                    event_type
+                ))))
+            }
+        };
+
+        let consumed = event.get_event_size() as usize;
+        Ok((event, consumed))
+    }
+
+    /// Gets the size in bytes of this live message's wire representation.
+    ///
+    /// Returned as a `u32` (unlike the file-event `Event::get_event_size`, which is stuck at `u8`)
+    /// since a live SysEx message — a bulk/patch dump from a hardware device — can easily carry a
+    /// payload beyond 253 bytes, and `u8` would silently wrap the reported size.
+    ///
+    /// # Returns
+    /// The number of bytes this message occupies on the wire, including its status byte
+    fn get_event_size(&self) -> u32 {
+        match self {
+            LiveEvent::NoteOn(event) => event.get_event_size() as u32,
+            LiveEvent::NoteOff(event) => event.get_event_size() as u32,
+            LiveEvent::PolyphonicKeyPressure(event) => event.get_event_size() as u32,
+            LiveEvent::ControlChange(event) => event.get_event_size() as u32,
+            LiveEvent::ChannelPressure(event) => event.get_event_size() as u32,
+            LiveEvent::PitchBendChange(event) => event.get_event_size() as u32,
+            LiveEvent::Common(SystemCommon::TimeCodeQuarterFrame(_)) => 2,
+            LiveEvent::Common(SystemCommon::SongPositionPointer(_)) => 3,
+            LiveEvent::Common(SystemCommon::SongSelect(_)) => 2,
+            LiveEvent::Common(SystemCommon::TuneRequest) => 1,
+            LiveEvent::RealTime(_) => 1,
+            LiveEvent::SysEx(sysex) => 2 + sysex.data.len() as u32,
+        }
+    }
+
+    /// Parse a single live MIDI message, discarding the consumed byte count.
+    ///
+    /// A convenience wrapper around [`LiveEvent::from_bytes`] for callers driving a MIDI I/O port
+    /// who only need the decoded message and don't care how many bytes it occupied on the wire.
+    ///
+    /// # Arguments
+    /// * `data` The bytes to parse, starting with a status byte
+    ///
+    /// # Returns
+    /// The parsed `LiveEvent`
+    pub fn parse(data: &[u8]) -> Result<Self, MidiError> {
+        Self::from_bytes(data).map(|(event, _consumed)| event)
+    }
+
+    /// Encode this live message back into its wire representation (status byte plus data bytes,
+    /// with no delta time).
+    ///
+    /// # Returns
+    /// The encoded bytes
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MidiError> {
+        match self {
+            LiveEvent::NoteOn(event) => event.to_bytes(),
+            LiveEvent::NoteOff(event) => event.to_bytes(),
+            LiveEvent::PolyphonicKeyPressure(event) => event.to_bytes(),
+            LiveEvent::ControlChange(event) => event.to_bytes(),
+            LiveEvent::ChannelPressure(event) => event.to_bytes(),
+            LiveEvent::PitchBendChange(event) => event.to_bytes(),
+            LiveEvent::Common(common) => Ok(match common {
+                SystemCommon::TimeCodeQuarterFrame(value) => vec![0xF1, *value],
+                SystemCommon::SongPositionPointer(position) => {
+                    vec![0xF2, (position & 0x7F) as u8, ((position >> 7) & 0x7F) as u8]
+                }
+                SystemCommon::SongSelect(song) => vec![0xF3, *song],
+                SystemCommon::TuneRequest => vec![0xF6],
+            }),
+            LiveEvent::RealTime(real_time) => Ok(vec![match real_time {
+                SystemRealTime::Clock => 0xF8,
+                SystemRealTime::Start => 0xFA,
+                SystemRealTime::Continue => 0xFB,
+                SystemRealTime::Stop => 0xFC,
+                SystemRealTime::ActiveSensing => 0xFE,
+                SystemRealTime::Reset => 0xFF,
+            }]),
+            LiveEvent::SysEx(sysex) => {
+                let mut bytes = Vec::with_capacity(2 + sysex.data.len());
+                bytes.push(0xF0);
+                bytes.extend_from_slice(&sysex.data);
+                bytes.push(0xF7);
+                Ok(bytes)
+            }
+        }
+    }
+}
+
+fn too_short() -> MidiError {
+    MidiError::ParseError(ParseError::InvalidEventBytes(
+        "Not enough bytes to parse this live event".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_note_on_with_no_delta_time() {
+        let (event, consumed) = LiveEvent::from_bytes(&[0x90, 60, 100]).unwrap();
+        assert_eq!(consumed, 3);
+        assert!(matches!(event, LiveEvent::NoteOn(_)));
+    }
+
+    #[test]
+    fn parses_system_common_messages() {
+        let (event, consumed) = LiveEvent::from_bytes(&[0xF2, 0x10, 0x20]).unwrap();
+        assert_eq!(consumed, 3);
+        assert_eq!(
+            event,
+            LiveEvent::Common(SystemCommon::SongPositionPointer(0x10 | (0x20 << 7)))
+        );
+
+        let (event, consumed) = LiveEvent::from_bytes(&[0xF6]).unwrap();
+        assert_eq!(consumed, 1);
+        assert_eq!(event, LiveEvent::Common(SystemCommon::TuneRequest));
+    }
+
+    #[test]
+    fn parses_system_real_time_messages_without_disturbing_longer_reads() {
+        let (event, consumed) = LiveEvent::from_bytes(&[0xF8, 0x90, 60, 100]).unwrap();
+        assert_eq!(consumed, 1);
+        assert_eq!(event, LiveEvent::RealTime(SystemRealTime::Clock));
+    }
+
+    #[test]
+    fn round_trips_a_sysex_message() {
+        let bytes = [0xF0, 0x41, 0x10, 0x42, 0xF7];
+        let (event, consumed) = LiveEvent::from_bytes(&bytes).unwrap();
+        assert_eq!(consumed, bytes.len());
+        assert_eq!(
+            event,
+            LiveEvent::SysEx(SystemExclusive { data: vec![0x41, 0x10, 0x42] })
+        );
+        assert_eq!(event.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn sysex_get_event_size_does_not_truncate_past_u8_range() {
+        let event = LiveEvent::SysEx(SystemExclusive { data: vec![0x10u8; 300] });
+        assert_eq!(event.get_event_size(), 302);
+    }
+
+    #[test]
+    fn sysex_missing_terminator_is_an_error() {
+        let result = LiveEvent::from_bytes(&[0xF0, 0x41, 0x10]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_convenience_wrapper_discards_consumed_count() {
+        let event = LiveEvent::parse(&[0xFE]).unwrap();
+        assert_eq!(event, LiveEvent::RealTime(SystemRealTime::ActiveSensing));
+    }
+}
@@ -11,6 +11,9 @@ pub use note_off::NoteOffEvent;
 mod control_change;
 pub use control_change::ControlChangeEvent;
 
+mod controller;
+pub use controller::{Controller, ControllerTracker, ParameterChange, ParameterKind};
+
 mod polyphonic_key_pressure;
 pub use polyphonic_key_pressure::PolyphonicKeyPressureEvent;
 
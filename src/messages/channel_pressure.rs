@@ -1,10 +1,15 @@
-use std::time::{Duration, Instant};
+use core::time::Duration;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use alloc::{string::String, vec, vec::Vec};
+
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
 
 use crate::{
-    events::{Event, FromBytes},
+    events::{require_len, Event, FromBytes},
     metadata::TimeDivision,
     metaevents::{calculate_time_duration, from_vlq_to_bytes},
     midi_error::MidiError,
@@ -17,14 +22,15 @@ const MIDI_EVENT_TYPE: u8 = 0xD0;
 const CHANNEL_PRESSURE_SIZE: u8 = 0x02;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct ChannelPressureEvent {
     pub pressure_value: u8,
     pub channel: u8,
     event_size: u8,
     delta_time: u32,
 
-    #[cfg_attr(feature = "json", serde(skip))]
+    #[cfg_attr(feature = "use_serde", serde(skip, default = "std::time::Instant::now"))]
+    #[cfg(feature = "std")]
     current_time: Instant,
     time_duration: Duration,
 }
@@ -35,7 +41,7 @@ impl Event for ChannelPressureEvent {
     }
 
     fn is_running_status_allowed(&self) -> bool {
-        false
+        true
     }
 
     fn event_type(&self) -> u8 {
@@ -54,6 +60,12 @@ impl Event for ChannelPressureEvent {
         self.delta_time
     }
 
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        self.delta_time = delta_time;
+        self.time_duration = calculate_time_duration(delta_time, time_division, tempo);
+    }
+
+    #[cfg(feature = "std")]
     fn get_current_time(&self) -> Instant {
         self.current_time
     }
@@ -75,7 +87,7 @@ impl Event for ChannelPressureEvent {
         Ok(bytes)
     }
 
-    fn as_any(&self) -> &dyn std::any::Any {
+    fn as_any(&self) -> &dyn core::any::Any {
         self
     }
 }
@@ -89,6 +101,8 @@ impl FromBytes for ChannelPressureEvent {
         time_division: TimeDivision,
         tempo: u32,
     ) -> Result<Self::Output, MidiError> {
+        require_len(data, 2, "Channel Pressure")?;
+
         // Calculate the time duration
         let time_duration = calculate_time_duration(delta_time, time_division, tempo);
 
@@ -108,6 +122,34 @@ impl FromBytes for ChannelPressureEvent {
             channel,
             event_size: CHANNEL_PRESSURE_SIZE,
             delta_time,
+            #[cfg(feature = "std")]
+            current_time: Instant::now(),
+            time_duration,
+        })
+    }
+}
+
+impl ChannelPressureEvent {
+    /// Build a `ChannelPressureEvent` from its one data byte when the status byte was inherited
+    /// from MIDI running status rather than appearing in `data`.
+    pub fn new_from_status(
+        data: &[u8],
+        delta_time: u32,
+        channel: u8,
+        time_division: TimeDivision,
+        tempo: u32,
+    ) -> Result<Self, MidiError> {
+        require_len(data, 1, "Channel Pressure")?;
+
+        // Calculate the time duration
+        let time_duration = calculate_time_duration(delta_time, time_division, tempo);
+
+        Ok(Self {
+            pressure_value: data[0],
+            channel,
+            event_size: CHANNEL_PRESSURE_SIZE,
+            delta_time,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration,
         })
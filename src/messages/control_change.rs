@@ -1,23 +1,28 @@
-use std::time::{Duration, Instant};
+use core::time::Duration;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use alloc::{string::String, vec, vec::Vec};
+
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
 
 use crate::{
-    events::{Event, FromBytes},
+    events::{require_len, Event, FromBytes},
     metadata::TimeDivision,
     metaevents::{calculate_time_duration, from_vlq_to_bytes},
     midi_error::MidiError,
 };
 
-use super::CHANNEL_MASK;
+use super::{controller::Controller, CHANNEL_MASK};
 
 const MIDI_EVENT_TYPE: u8 = 0xB0;
 
 const CONTROL_CHANGE_SIZE: u8 = 0x03;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct ControlChangeEvent {
     pub contol: u8,
     pub control_value: u8,
@@ -25,7 +30,8 @@ pub struct ControlChangeEvent {
     event_size: u8,
     delta_time: u32,
 
-    #[cfg_attr(feature = "json", serde(skip))]
+    #[cfg_attr(feature = "use_serde", serde(skip, default = "std::time::Instant::now"))]
+    #[cfg(feature = "std")]
     current_time: Instant,
     time_duration: Duration,
 }
@@ -55,6 +61,12 @@ impl Event for ControlChangeEvent {
         self.delta_time
     }
 
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        self.delta_time = delta_time;
+        self.time_duration = calculate_time_duration(delta_time, time_division, tempo);
+    }
+
+    #[cfg(feature = "std")]
     fn get_current_time(&self) -> Instant {
         self.current_time
     }
@@ -84,7 +96,7 @@ impl Event for ControlChangeEvent {
         Ok(bytes)
     }
 
-    fn as_any(&self) -> &dyn std::any::Any {
+    fn as_any(&self) -> &dyn core::any::Any {
         self
     }
 }
@@ -98,6 +110,8 @@ impl FromBytes for ControlChangeEvent {
         time_division: TimeDivision,
         tempo: u32,
     ) -> Result<Self::Output, MidiError> {
+        require_len(data, 3, "Control Change")?;
+
         // Calculate the time duration
         let time_duration = calculate_time_duration(delta_time, time_division, tempo);
 
@@ -121,6 +135,7 @@ impl FromBytes for ControlChangeEvent {
             channel,
             event_size: CONTROL_CHANGE_SIZE,
             delta_time,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration,
         })
@@ -134,6 +149,8 @@ impl<'a> ControlChangeEvent {
         time_division: TimeDivision,
         tempo: u32,
     ) -> Result<Self, MidiError> {
+        require_len(data, 2, "Control Change")?;
+
         let delta_time = 0;
 
         // Calculate the time duration
@@ -145,8 +162,14 @@ impl<'a> ControlChangeEvent {
             channel,
             event_size: CONTROL_CHANGE_SIZE,
             delta_time,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration,
         })
     }
+
+    /// The semantic meaning of this event's raw controller number.
+    pub fn controller(&self) -> Controller {
+        Controller::from_number(self.contol)
+    }
 }
@@ -0,0 +1,239 @@
+use super::ControlChangeEvent;
+
+/// Semantic names for the standard MIDI Control Change controller numbers (CC 0-127).
+/// `Other` holds any controller number this crate doesn't give a name to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Controller {
+    BankSelectMsb,
+    Modulation,
+    BreathController,
+    FootController,
+    PortamentoTime,
+    DataEntryMsb,
+    Volume,
+    Balance,
+    Pan,
+    Expression,
+    BankSelectLsb,
+    DataEntryLsb,
+    Sustain,
+    Portamento,
+    Sostenuto,
+    SoftPedal,
+    NrpnLsb,
+    NrpnMsb,
+    RpnLsb,
+    RpnMsb,
+    AllSoundOff,
+    ResetAllControllers,
+    LocalControl,
+    AllNotesOff,
+    Other(u8),
+}
+
+impl Controller {
+    /// Look up the semantic name for a raw controller number, falling back to `Other`.
+    pub fn from_number(number: u8) -> Self {
+        match number {
+            0 => Controller::BankSelectMsb,
+            1 => Controller::Modulation,
+            2 => Controller::BreathController,
+            4 => Controller::FootController,
+            5 => Controller::PortamentoTime,
+            6 => Controller::DataEntryMsb,
+            7 => Controller::Volume,
+            8 => Controller::Balance,
+            10 => Controller::Pan,
+            11 => Controller::Expression,
+            32 => Controller::BankSelectLsb,
+            38 => Controller::DataEntryLsb,
+            64 => Controller::Sustain,
+            65 => Controller::Portamento,
+            66 => Controller::Sostenuto,
+            67 => Controller::SoftPedal,
+            98 => Controller::NrpnLsb,
+            99 => Controller::NrpnMsb,
+            100 => Controller::RpnLsb,
+            101 => Controller::RpnMsb,
+            120 => Controller::AllSoundOff,
+            121 => Controller::ResetAllControllers,
+            122 => Controller::LocalControl,
+            123 => Controller::AllNotesOff,
+            other => Controller::Other(other),
+        }
+    }
+
+    /// The raw controller number this variant represents.
+    pub fn number(&self) -> u8 {
+        match self {
+            Controller::BankSelectMsb => 0,
+            Controller::Modulation => 1,
+            Controller::BreathController => 2,
+            Controller::FootController => 4,
+            Controller::PortamentoTime => 5,
+            Controller::DataEntryMsb => 6,
+            Controller::Volume => 7,
+            Controller::Balance => 8,
+            Controller::Pan => 10,
+            Controller::Expression => 11,
+            Controller::BankSelectLsb => 32,
+            Controller::DataEntryLsb => 38,
+            Controller::Sustain => 64,
+            Controller::Portamento => 65,
+            Controller::Sostenuto => 66,
+            Controller::SoftPedal => 67,
+            Controller::NrpnLsb => 98,
+            Controller::NrpnMsb => 99,
+            Controller::RpnLsb => 100,
+            Controller::RpnMsb => 101,
+            Controller::AllSoundOff => 120,
+            Controller::ResetAllControllers => 121,
+            Controller::LocalControl => 122,
+            Controller::AllNotesOff => 123,
+            Controller::Other(number) => *number,
+        }
+    }
+}
+
+/// Which 14-bit parameter number space a resolved [`ParameterChange`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterKind {
+    /// A Registered Parameter Number, e.g. `(0, 0)` for Pitch Bend Sensitivity.
+    Rpn,
+    /// A Non-Registered Parameter Number, vendor/instrument specific.
+    Nrpn,
+}
+
+/// A resolved Registered/Non-Registered Parameter Number change, coalesced from the
+/// MSB/LSB/Data-Entry Control Change sequence that encodes it on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParameterChange {
+    pub kind: ParameterKind,
+    /// The 14-bit parameter number, e.g. `0` for Pitch Bend Sensitivity under `Rpn`.
+    pub parameter: u16,
+    /// The 14-bit value written to that parameter.
+    pub value: u16,
+}
+
+/// Folds a sequence of `ControlChangeEvent`s into resolved [`ParameterChange`]s, tracking the
+/// RPN/NRPN MSB/LSB/Data-Entry state machine across events on a single channel.
+///
+/// A parameter change resolves once its MSB, LSB, and a Data Entry MSB/LSB pair have all been
+/// seen, in the order a real controller stream sends them.
+#[derive(Debug, Default)]
+pub struct ControllerTracker {
+    parameter_kind: Option<ParameterKind>,
+    parameter_msb: Option<u8>,
+    parameter_lsb: Option<u8>,
+    value_msb: Option<u8>,
+}
+
+impl ControllerTracker {
+    /// Create a new `ControllerTracker` with no parameter number established yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next `ControlChangeEvent` in sequence, returning a resolved `ParameterChange`
+    /// once its Data Entry LSB arrives.
+    pub fn feed(&mut self, event: &ControlChangeEvent) -> Option<ParameterChange> {
+        match event.controller() {
+            Controller::RpnMsb => {
+                self.parameter_kind = Some(ParameterKind::Rpn);
+                self.parameter_msb = Some(event.control_value);
+                self.value_msb = None;
+                None
+            }
+            Controller::RpnLsb => {
+                self.parameter_kind = Some(ParameterKind::Rpn);
+                self.parameter_lsb = Some(event.control_value);
+                self.value_msb = None;
+                None
+            }
+            Controller::NrpnMsb => {
+                self.parameter_kind = Some(ParameterKind::Nrpn);
+                self.parameter_msb = Some(event.control_value);
+                self.value_msb = None;
+                None
+            }
+            Controller::NrpnLsb => {
+                self.parameter_kind = Some(ParameterKind::Nrpn);
+                self.parameter_lsb = Some(event.control_value);
+                self.value_msb = None;
+                None
+            }
+            Controller::DataEntryMsb => {
+                self.value_msb = Some(event.control_value);
+                None
+            }
+            Controller::DataEntryLsb => {
+                let kind = self.parameter_kind?;
+                let parameter_msb = self.parameter_msb?;
+                let parameter_lsb = self.parameter_lsb?;
+                let value_msb = self.value_msb?;
+
+                Some(ParameterChange {
+                    kind,
+                    parameter: ((parameter_msb as u16) << 7) | parameter_lsb as u16,
+                    value: ((value_msb as u16) << 7) | event.control_value as u16,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::TimeDivision;
+
+    fn control_change(control: u8, value: u8) -> ControlChangeEvent {
+        ControlChangeEvent::new_from_status(
+            &[control, value],
+            0,
+            TimeDivision::PulsesPerQuarterNote(96),
+            120,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_controller_from_number_known() {
+        assert_eq!(Controller::from_number(7), Controller::Volume);
+        assert_eq!(Controller::from_number(64), Controller::Sustain);
+    }
+
+    #[test]
+    fn test_controller_from_number_unknown() {
+        assert_eq!(Controller::from_number(3), Controller::Other(3));
+    }
+
+    #[test]
+    fn test_controller_tracker_resolves_rpn_pitch_bend_sensitivity() {
+        let mut tracker = ControllerTracker::new();
+
+        assert!(tracker.feed(&control_change(101, 0)).is_none());
+        assert!(tracker.feed(&control_change(100, 0)).is_none());
+        assert!(tracker.feed(&control_change(6, 2)).is_none());
+
+        let change = tracker.feed(&control_change(38, 0)).unwrap();
+        assert_eq!(change.kind, ParameterKind::Rpn);
+        assert_eq!(change.parameter, 0);
+        assert_eq!(change.value, 2 << 7);
+    }
+
+    #[test]
+    fn test_controller_tracker_resolves_nrpn() {
+        let mut tracker = ControllerTracker::new();
+
+        tracker.feed(&control_change(99, 1));
+        tracker.feed(&control_change(98, 5));
+        tracker.feed(&control_change(6, 10));
+        let change = tracker.feed(&control_change(38, 20)).unwrap();
+
+        assert_eq!(change.kind, ParameterKind::Nrpn);
+        assert_eq!(change.parameter, (1 << 7) | 5);
+        assert_eq!(change.value, (10 << 7) | 20);
+    }
+}
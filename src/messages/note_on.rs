@@ -1,12 +1,15 @@
-use std::{
-    time::{Duration, Instant},
-};
+use core::time::Duration;
+
+#[cfg(feature = "std")]
+use std::time::Instant;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+use alloc::{string::{String, ToString}, vec, vec::Vec};
+
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
 
 use crate::{
-    events::{Event, FromBytes},
+    events::{require_len, Event, FromBytes},
     metadata::TimeDivision,
     metaevents::{calculate_time_duration, from_vlq_to_bytes},
     midi_error::MidiError,
@@ -24,7 +27,7 @@ const NOTE_ON_SIZE: u8 = 0x03;
 const EVENT_NAME: &str = "Note On";
 
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct NoteOnEvent {
     pub midi_note: u8,
     pub velocity: u8,
@@ -33,7 +36,8 @@ pub struct NoteOnEvent {
     event_size: u8,
     delta_time: u32,
     
-    #[cfg_attr(feature = "json", serde(skip))]
+    #[cfg_attr(feature = "use_serde", serde(skip, default = "std::time::Instant::now"))]
+    #[cfg(feature = "std")]
     current_time: Instant,
     time_duration: Duration,
 }
@@ -63,6 +67,12 @@ impl Event for NoteOnEvent {
         self.delta_time
     }
 
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        self.delta_time = delta_time;
+        self.time_duration = calculate_time_duration(delta_time, time_division, tempo);
+    }
+
+    #[cfg(feature = "std")]
     fn get_current_time(&self) -> Instant {
         self.current_time
     }
@@ -92,7 +102,7 @@ impl Event for NoteOnEvent {
         Ok(bytes)
     }
 
-    fn as_any(&self) -> &dyn std::any::Any {
+    fn as_any(&self) -> &dyn core::any::Any {
         self
     }
 }
@@ -106,6 +116,8 @@ impl FromBytes for NoteOnEvent {
         time_division: TimeDivision,
         tempo: u32,
     ) -> Result<Self::Output, MidiError> {
+        require_len(data, 3, EVENT_NAME)?;
+
         // Calculate the time duration
         let time_duration = calculate_time_duration(delta_time, time_division, tempo);
 
@@ -130,6 +142,7 @@ impl FromBytes for NoteOnEvent {
             event_name: EVENT_NAME.to_string(),
             event_size: NOTE_ON_SIZE,
             delta_time,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration,
         })
@@ -145,6 +158,7 @@ impl<'a> NoteOnEvent {
             event_name: EVENT_NAME.to_string(),
             event_size: NOTE_ON_SIZE,
             delta_time: 0,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration: Duration::from_secs(0),
         })
@@ -158,6 +172,7 @@ impl<'a> NoteOnEvent {
             event_name: EVENT_NAME.to_string(),
             event_size: NOTE_ON_SIZE,
             delta_time: 0,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration: Duration::from_secs(0),
         })
@@ -168,6 +183,8 @@ impl<'a> NoteOnEvent {
     }
 
     pub fn new_from_status(data: &[u8], delta_time: u32, channel: u8, time_division: TimeDivision, tempo: u32) -> Result<Self, MidiError> {
+        require_len(data, 2, EVENT_NAME)?;
+
         // Calculate the time duration
         let time_duration = calculate_time_duration(delta_time, time_division, tempo);
 
@@ -179,6 +196,7 @@ impl<'a> NoteOnEvent {
                 event_name: EVENT_NAME.to_string(),
                 event_size: NOTE_ON_SIZE,
                 delta_time,
+                #[cfg(feature = "std")]
                 current_time: Instant::now(),
                 time_duration,
             }
@@ -1,10 +1,15 @@
-use std::time::{Duration, Instant};
+use core::time::Duration;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use alloc::{string::String, vec, vec::Vec};
+
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
 
 use crate::{
-    events::{Event, FromBytes},
+    events::{require_len, Event, FromBytes},
     metadata::TimeDivision,
     metaevents::{calculate_time_duration, from_vlq_to_bytes},
     midi_error::MidiError,
@@ -17,7 +22,7 @@ const MIDI_EVENT_TYPE: u8 = 0xA0;
 const POLYPHONIC_KEY_PRESSURE_SIZE: u8 = 0x03;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct PolyphonicKeyPressureEvent {
     pub midi_note: u8,
     pub control_pressure: u8,
@@ -25,7 +30,8 @@ pub struct PolyphonicKeyPressureEvent {
     event_size: u8,
     delta_time: u32,
 
-    #[cfg_attr(feature = "json", serde(skip))]
+    #[cfg_attr(feature = "use_serde", serde(skip, default = "std::time::Instant::now"))]
+    #[cfg(feature = "std")]
     current_time: Instant,
     time_duration: Duration,
 }
@@ -55,6 +61,12 @@ impl Event for PolyphonicKeyPressureEvent {
         self.delta_time
     }
 
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        self.delta_time = delta_time;
+        self.time_duration = calculate_time_duration(delta_time, time_division, tempo);
+    }
+
+    #[cfg(feature = "std")]
     fn get_current_time(&self) -> Instant {
         self.current_time
     }
@@ -84,7 +96,7 @@ impl Event for PolyphonicKeyPressureEvent {
         Ok(bytes)
     }
 
-    fn as_any(&self) -> &dyn std::any::Any {
+    fn as_any(&self) -> &dyn core::any::Any {
         self
     }
 }
@@ -98,6 +110,8 @@ impl FromBytes for PolyphonicKeyPressureEvent {
         time_division: TimeDivision,
         tempo: u32,
     ) -> Result<Self::Output, MidiError> {
+        require_len(data, 3, "Polyphonic Key Pressure")?;
+
         // Calculate the time duration
         let time_duration = calculate_time_duration(delta_time, time_division, tempo);
 
@@ -121,6 +135,35 @@ impl FromBytes for PolyphonicKeyPressureEvent {
             channel,
             event_size: POLYPHONIC_KEY_PRESSURE_SIZE,
             delta_time,
+            #[cfg(feature = "std")]
+            current_time: Instant::now(),
+            time_duration,
+        })
+    }
+}
+
+impl PolyphonicKeyPressureEvent {
+    /// Build a `PolyphonicKeyPressureEvent` from its two data bytes when the status byte was
+    /// inherited from MIDI running status rather than appearing in `data`.
+    pub fn new_from_status(
+        data: &[u8],
+        delta_time: u32,
+        channel: u8,
+        time_division: TimeDivision,
+        tempo: u32,
+    ) -> Result<Self, MidiError> {
+        require_len(data, 2, "Polyphonic Key Pressure")?;
+
+        // Calculate the time duration
+        let time_duration = calculate_time_duration(delta_time, time_division, tempo);
+
+        Ok(Self {
+            midi_note: data[0],
+            control_pressure: data[1],
+            channel,
+            event_size: POLYPHONIC_KEY_PRESSURE_SIZE,
+            delta_time,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration,
         })
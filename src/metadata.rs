@@ -1,17 +1,22 @@
 use crate::midi_error::{MidiError, ParseError};
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
 
 const FILE_FORMAT_BYTES: std::ops::Range<usize> = 8..10;
 const NUM_OF_TRACKS_BYTES: std::ops::Range<usize> = 10..12;
 const TIME_DIVISION_BYTES: std::ops::Range<usize> = 12..14;
 
 const HEADER_CHUNK_MTHD_BYTES: u32 = u32::from_be_bytes([0x4D, 0x54, 0x68, 0x64]);
+const HEADER_SIZE_BYTES: [u8; 4] = [0x00, 0x00, 0x00, 0x06];
+
+/// The total length in bytes of an `MThd` header chunk: the `"MThd"` magic, the fixed chunk
+/// length, and the format/num-tracks/division words.
+const HEADER_CHUNK_LEN: usize = 14;
 
 #[derive(Debug, Clone, PartialEq)]
 #[allow(non_camel_case_types)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub enum FileFormat {
     SINGLE_TRACK,
     MULTI_TRACK,
@@ -19,14 +24,14 @@ pub enum FileFormat {
 }
 
 #[derive(Debug, Clone, PartialEq, Copy)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub enum TimeDivision {
     PulsesPerQuarterNote(u16),
     SMPTE(u8, u8),
 }
 
 #[derive(Debug, Clone)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct MetaData {
     pub num_of_tracks: u16,
     pub file_format: FileFormat,
@@ -35,6 +40,10 @@ pub struct MetaData {
 
 impl MetaData {
     pub fn new(file_contents: &[u8]) -> Result<Self, MidiError> {
+        if file_contents.len() < HEADER_CHUNK_LEN {
+            return Err(MidiError::FileError(HEADER_CHUNK_LEN));
+        }
+
         let mut meta_data = Self {
             num_of_tracks: 0,
             file_format: FileFormat::SINGLE_TRACK,
@@ -50,6 +59,53 @@ impl MetaData {
         Ok(meta_data)
     }
 
+    /// Parse a `MetaData` by reading the 14-byte `MThd` header directly from a `Read` source,
+    /// rather than requiring the whole file to already be loaded into a `&[u8]`.
+    ///
+    /// ### Arguments
+    /// * `reader` The source to read the header bytes from
+    ///
+    /// ### Returns
+    /// The parsed `MetaData`
+    #[cfg(feature = "std")]
+    pub fn new_from_reader<R: std::io::Read>(reader: &mut R) -> Result<Self, MidiError> {
+        let mut header = [0u8; HEADER_CHUNK_LEN];
+        reader.read_exact(&mut header)?;
+
+        Self::new(&header)
+    }
+
+    /// Encode this `MetaData` back into the full `MThd` header chunk it was parsed from (or would
+    /// have been parsed from).
+    ///
+    /// ### Returns
+    /// The 14-byte `MThd` header chunk
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_CHUNK_LEN);
+        bytes.extend_from_slice(&HEADER_CHUNK_MTHD_BYTES.to_be_bytes());
+        bytes.extend_from_slice(&HEADER_SIZE_BYTES);
+
+        let format_word: u16 = match self.file_format {
+            FileFormat::SINGLE_TRACK => 0,
+            FileFormat::MULTI_TRACK => 1,
+            FileFormat::MULTI_SONG => 2,
+        };
+        bytes.extend_from_slice(&format_word.to_be_bytes());
+        bytes.extend_from_slice(&self.num_of_tracks.to_be_bytes());
+
+        match self.time_division {
+            TimeDivision::PulsesPerQuarterNote(ticks_per_quarter_note) => {
+                bytes.extend_from_slice(&ticks_per_quarter_note.to_be_bytes());
+            }
+            TimeDivision::SMPTE(fps, ticks_per_frame) => {
+                bytes.push((-(fps as i8)) as u8);
+                bytes.push(ticks_per_frame);
+            }
+        }
+
+        bytes
+    }
+
     fn get_num_of_tracks(&mut self, file_contents: &[u8]) -> Result<(), MidiError> {
         let num_of_track = &file_contents[NUM_OF_TRACKS_BYTES];
         let num_of_tracks = u16::from_be_bytes([num_of_track[0], num_of_track[1]]);
@@ -121,8 +177,6 @@ mod metadata_tests {
     use super::*;
     use env_logger;
 
-    const HEADER_SIZE_BYTES: [u8; 4] = [0x00, 0x00, 0x00, 0x06];
-
     fn setup() {
         let _ = env_logger::builder()
             .filter_level(log::LevelFilter::Debug)
@@ -214,5 +268,59 @@ mod metadata_tests {
         assert!(metadata_result.is_ok());
         assert_eq!(metadata_result.unwrap().time_division, TimeDivision::SMPTE(24, 8));
     }
-    
+
+    #[test]
+    fn validate_time_division_smpte_25fps_40_ticks_round_trip() {
+        // -25fps, 40 ticks per frame: high byte is -25 as an i8 (0xE7), low byte is the tick count
+        let mut header_chunk_time_div: Vec<u8> = HEADER_CHUNK_MTHD_BYTES.to_be_bytes().to_vec();
+        header_chunk_time_div.extend_from_slice(&HEADER_SIZE_BYTES);
+        header_chunk_time_div.extend_from_slice(&[0x00, 0x01, 0x00, 0x01, 0xE7, 0x28]);
+
+        let metadata_result = MetaData::new(&header_chunk_time_div);
+        assert!(metadata_result.is_ok());
+        assert_eq!(metadata_result.unwrap().time_division, TimeDivision::SMPTE(25, 40));
+    }
+
+    #[test]
+    fn new_fails_on_truncated_input_instead_of_panicking() {
+        let header_chunk_time_div: Vec<u8> = HEADER_CHUNK_MTHD_BYTES.to_be_bytes().to_vec();
+
+        let metadata_result = MetaData::new(&header_chunk_time_div);
+        assert!(matches!(metadata_result, Err(MidiError::FileError(HEADER_CHUNK_LEN))));
+    }
+
+    #[test]
+    fn to_bytes_round_trips_pulses_per_quarter_note() {
+        let mut header_chunk: Vec<u8> = HEADER_CHUNK_MTHD_BYTES.to_be_bytes().to_vec();
+        header_chunk.extend_from_slice(&HEADER_SIZE_BYTES);
+        header_chunk.extend_from_slice(&[0x00, 0x01, 0x00, 0x01, 0x00, 0x60]);
+
+        let metadata = MetaData::new(&header_chunk).unwrap();
+        assert_eq!(metadata.to_bytes(), header_chunk);
+    }
+
+    #[test]
+    fn to_bytes_round_trips_smpte() {
+        let mut header_chunk: Vec<u8> = HEADER_CHUNK_MTHD_BYTES.to_be_bytes().to_vec();
+        header_chunk.extend_from_slice(&HEADER_SIZE_BYTES);
+        header_chunk.extend_from_slice(&[0x00, 0x01, 0x00, 0x01, 0xE7, 0x28]);
+
+        let metadata = MetaData::new(&header_chunk).unwrap();
+        assert_eq!(metadata.to_bytes(), header_chunk);
+    }
+
+    #[test]
+    fn new_from_reader_reads_only_the_header_bytes() {
+        let mut header_chunk: Vec<u8> = HEADER_CHUNK_MTHD_BYTES.to_be_bytes().to_vec();
+        header_chunk.extend_from_slice(&HEADER_SIZE_BYTES);
+        header_chunk.extend_from_slice(&[0x00, 0x01, 0x00, 0x01, 0x00, 0x60]);
+        header_chunk.extend_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+
+        let mut reader: &[u8] = &header_chunk;
+        let metadata = MetaData::new_from_reader(&mut reader).unwrap();
+
+        assert_eq!(metadata.time_division, TimeDivision::PulsesPerQuarterNote(96));
+        // Only the 14-byte header should have been consumed from the reader.
+        assert_eq!(reader, &[0xDE, 0xAD, 0xBE, 0xEF][..]);
+    }
 }
\ No newline at end of file
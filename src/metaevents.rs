@@ -1,4 +1,6 @@
-use std::time::Duration;
+use core::time::Duration;
+
+use alloc::{string::String, vec::Vec};
 
 use crate::{midi_error::MidiError, metadata::TimeDivision};
 
@@ -45,6 +47,7 @@ pub use key_signature::KeySignature;
 
 mod smpte_offset;
 pub use smpte_offset::SMPTEOffsetEvent;
+pub use smpte_offset::SmpteFrameRate;
 
 mod sequencer_specific;
 pub use sequencer_specific::SequencerSpecificEvent;
@@ -97,6 +100,27 @@ pub(crate) fn from_vlq_to_bytes(delta_time: u32) -> Vec<u8> {
     bytes
 }
 
+/// Same as [`from_vlq_to_bytes`], but writes the encoded bytes straight to `w` instead of
+/// allocating a `Vec` to hold them.
+#[cfg(feature = "std")]
+pub(crate) fn write_vlq<W: std::io::Write>(w: &mut W, value: u32) -> std::io::Result<()> {
+    let mut value = value;
+
+    loop {
+        let mut byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value > 0 {
+            byte |= 0x80;
+        }
+        w.write_all(&[byte])?;
+        if value == 0 {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
 /// Given an array of u8 bytes, find out the delta time of variable-length format
 /// Variable-length format in essence says that if the first bit of the byte (MSB)
 /// is a 1 then the next byte is a part of the delta time. Otherwise that byte is a
@@ -136,17 +160,22 @@ pub(crate) fn from_bytes_to_vlq(track_data: &[u8]) -> (u32, u8) {
 ///
 /// A `Duration` object representing the calculated time duration.
 pub(crate) fn calculate_time_duration(delta_time: u32, time_division: TimeDivision, tempo: u32) -> Duration {
-    let ticks_per_quarter_note: u16 = match time_division {
-        TimeDivision::PulsesPerQuarterNote(pulses) => pulses,
-        TimeDivision::SMPTE(fps, ticks_per_frame) => (fps * ticks_per_frame / 4) as u16,
-    };
+    match time_division {
+        TimeDivision::PulsesPerQuarterNote(ticks_per_quarter_note) => {
+            let microseconds_per_quarter_note = 60_000_000 / tempo;
+            let seconds_per_quarter_note = microseconds_per_quarter_note as f32 / 1_000_000.0;
+            let time_duration_ticks = delta_time as f32 / ticks_per_quarter_note as f32;
 
-    let microseconds_per_quarter_note = 60_000_000 / tempo;
-    let seconds_per_quarter_note = microseconds_per_quarter_note as f32 / 1_000_000.0;
-    let time_duration_ticks = delta_time as f32 / ticks_per_quarter_note as f32;
-    let time_duration_seconds = time_duration_ticks * seconds_per_quarter_note;
+            Duration::from_secs_f32(time_duration_ticks * seconds_per_quarter_note)
+        }
+        TimeDivision::SMPTE(fps, ticks_per_frame) => {
+            // A SMPTE tick is a fixed wall-clock duration, independent of tempo. The 29 fps code
+            // actually means 29.97 drop-frame (30000/1001).
+            let effective_fps = if fps == 29 { 30_000.0 / 1_001.0 } else { fps as f64 };
 
-    Duration::from_secs_f32(time_duration_seconds)
+            Duration::from_secs_f64(delta_time as f64 / (effective_fps * ticks_per_frame as f64))
+        }
+    }
 }
 
 /// Converts a duration in microseconds to beats per minute (BPM).
@@ -179,6 +208,35 @@ pub(crate) fn bpm_to_microseconds(bpm: f64) -> u32 {
 mod metaevent_tests {
     use super::*;
 
+    #[test]
+    fn calculate_time_duration_smpte_success() {
+        // -25fps, 40 ticks per frame: each tick is 1ms, independent of tempo
+        let time_division = TimeDivision::SMPTE(25, 40);
+
+        let duration = calculate_time_duration(40, time_division, 120);
+        assert_eq!(duration, Duration::from_millis(40));
+    }
+
+    #[test]
+    fn calculate_time_duration_smpte_drop_frame_success() {
+        // -29fps actually means 29.97 drop-frame (30000/1001)
+        let time_division = TimeDivision::SMPTE(29, 2);
+        let tempo_a = calculate_time_duration(1, time_division, 60);
+        let tempo_b = calculate_time_duration(1, time_division, 180);
+
+        // SMPTE timing must not depend on tempo
+        assert_eq!(tempo_a, tempo_b);
+    }
+
+    #[test]
+    fn calculate_time_duration_smpte_matches_parsed_header_format() {
+        // Mirrors `validate_time_division_smpte_format_sucess`'s 24fps/8-ticks-per-frame header.
+        let time_division = TimeDivision::SMPTE(24, 8);
+
+        let duration = calculate_time_duration(192, time_division, 120);
+        assert_eq!(duration, Duration::from_secs(1));
+    }
+
     #[test]
     fn get_utf8_bytes_success() {
         let test_text = String::from("This is some testing text!! Yay! 1234567890,/\\!@#$%^&*()';\"[]");
@@ -1,10 +1,15 @@
-use std::time::{Duration, Instant};
+use core::time::Duration;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use alloc::{string::String, vec, vec::Vec};
+
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
 
 use crate::{
-    events::{Event, FromBytes},
+    events::{require_len, Event, FromBytes},
     metadata::TimeDivision,
     metaevents::get_utf8_from_bytes,
     midi_error::MidiError,
@@ -15,14 +20,15 @@ use super::{calculate_time_duration, from_bytes_to_vlq, from_vlq_to_bytes, METAE
 const METAEVENT_BYTE_TYPE: u8 = 0x02;
 
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct CopyRightNoticeEvent {
     pub copyright_notice: String,
     text_size: u32,
     event_size: u8,
     delta_time: u32,
 
-    #[cfg_attr(feature = "json", serde(skip))]
+    #[cfg_attr(feature = "use_serde", serde(skip, default = "std::time::Instant::now"))]
+    #[cfg(feature = "std")]
     current_time: Instant,
     time_duration: Duration,
 }
@@ -52,6 +58,12 @@ impl Event for CopyRightNoticeEvent {
         self.delta_time
     }
 
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        self.delta_time = delta_time;
+        self.time_duration = calculate_time_duration(delta_time, time_division, tempo);
+    }
+
+    #[cfg(feature = "std")]
     fn get_current_time(&self) -> Instant {
         self.current_time
     }
@@ -77,7 +89,7 @@ impl Event for CopyRightNoticeEvent {
         Ok(bytes)
     }
 
-    fn as_any(&self) -> &dyn std::any::Any {
+    fn as_any(&self) -> &dyn core::any::Any {
         self
     }
 }
@@ -91,6 +103,8 @@ impl FromBytes for CopyRightNoticeEvent {
         time_division: TimeDivision,
         tempo: u32,
     ) -> Result<Self::Output, MidiError> {
+        require_len(data, 2, "Copyright Notice")?;
+
         // Calculate the time duration
         let time_duration = calculate_time_duration(delta_time, time_division, tempo);
 
@@ -101,6 +115,11 @@ impl FromBytes for CopyRightNoticeEvent {
         let (data_length, num_of_bytes) = from_bytes_to_vlq(&data);
 
         // Ensure we are only taking the data and not anything else past that incase we are given more bytes to follow
+        require_len(
+            data,
+            num_of_bytes as usize + data_length as usize,
+            "Copyright Notice",
+        )?;
         let data = &data[(num_of_bytes as usize)..((num_of_bytes as u32 + data_length) as usize)];
 
         // Convert the bytes to UTF-8 text
@@ -114,6 +133,7 @@ impl FromBytes for CopyRightNoticeEvent {
             text_size: data_length,
             event_size,
             delta_time,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration,
         })
@@ -1,7 +1,12 @@
-use std::time::{Duration, Instant};
+use core::time::Duration;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use alloc::{string::String, vec, vec::Vec};
+
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
 
 use crate::{
     events::{Event, FromBytes},
@@ -16,12 +21,13 @@ const METAEVENT_BYTE_TYPE: u8 = 0x2F;
 const END_OF_TRACK_SIZE: u8 = 3;
 
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct EndOfTrack {
     event_size: u8,
     delta_time: u32,
 
-    #[cfg_attr(feature = "json", serde(skip))]
+    #[cfg_attr(feature = "use_serde", serde(skip, default = "std::time::Instant::now"))]
+    #[cfg(feature = "std")]
     current_time: Instant,
     time_duration: Duration,
 }
@@ -51,6 +57,12 @@ impl Event for EndOfTrack {
         self.delta_time
     }
 
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        self.delta_time = delta_time;
+        self.time_duration = calculate_time_duration(delta_time, time_division, tempo);
+    }
+
+    #[cfg(feature = "std")]
     fn get_current_time(&self) -> Instant {
         self.current_time
     }
@@ -74,7 +86,7 @@ impl Event for EndOfTrack {
         Ok(bytes)
     }
 
-    fn as_any(&self) -> &dyn std::any::Any {
+    fn as_any(&self) -> &dyn core::any::Any {
         self
     }
 }
@@ -95,6 +107,7 @@ impl FromBytes for EndOfTrack {
         Ok(Self {
             event_size: END_OF_TRACK_SIZE,
             delta_time,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration,
         })
@@ -106,6 +119,7 @@ impl EndOfTrack {
         Ok(Self {
             event_size: END_OF_TRACK_SIZE,
             delta_time: 0,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration: Duration::from_secs(0),
         })
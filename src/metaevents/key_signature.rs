@@ -1,10 +1,15 @@
-use std::time::{Duration, Instant};
+use core::time::Duration;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use alloc::{string::{String, ToString}, vec, vec::Vec, format};
+
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
 
 use crate::{
-    events::{Event, FromBytes},
+    events::{require_len, Event, FromBytes},
     metadata::TimeDivision,
     midi_error::{EventError, MidiError},
 };
@@ -16,7 +21,7 @@ const METAEVENT_BYTE_TYPE: u8 = 0x59;
 const KEY_SIGNATURE_SIZE: u8 = 5;
 
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub enum Mode {
     Major,
     Minor,
@@ -32,7 +37,7 @@ impl Mode {
 }
 
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub enum Accidentals {
     Flat,
     Natural,
@@ -40,7 +45,7 @@ pub enum Accidentals {
 }
 
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub enum Key {
     C,
     D,
@@ -52,7 +57,7 @@ pub enum Key {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct KeySignature {
     pub key: Key,
     pub accidental: Accidentals,
@@ -61,13 +66,14 @@ pub struct KeySignature {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct KeySignatureEvent {
     pub key_signature: KeySignature,
     event_size: u8,
     delta_time: u32,
 
-    #[cfg_attr(feature = "json", serde(skip))]
+    #[cfg_attr(feature = "use_serde", serde(skip, default = "std::time::Instant::now"))]
+    #[cfg(feature = "std")]
     current_time: Instant,
     time_duration: Duration,
 }
@@ -108,6 +114,12 @@ impl Event for KeySignatureEvent {
         self.delta_time
     }
 
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        self.delta_time = delta_time;
+        self.time_duration = calculate_time_duration(delta_time, time_division, tempo);
+    }
+
+    #[cfg(feature = "std")]
     fn get_current_time(&self) -> Instant {
         self.current_time
     }
@@ -141,7 +153,7 @@ impl Event for KeySignatureEvent {
         Ok(bytes)
     }
 
-    fn as_any(&self) -> &dyn std::any::Any {
+    fn as_any(&self) -> &dyn core::any::Any {
         self
     }
 }
@@ -155,6 +167,8 @@ impl FromBytes for KeySignatureEvent {
         time_division: TimeDivision,
         tempo: u32,
     ) -> Result<Self::Output, MidiError> {
+        require_len(data, 5, "Key Signature")?;
+
         // Calculate the time duration
         let time_duration = calculate_time_duration(delta_time, time_division, tempo);
 
@@ -176,6 +190,7 @@ impl FromBytes for KeySignatureEvent {
             key_signature: KeySignature::new(key, accidental, mode)?,
             event_size: KEY_SIGNATURE_SIZE,
             delta_time,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration,
         })
@@ -188,6 +203,7 @@ impl KeySignatureEvent {
             key_signature,
             event_size: KEY_SIGNATURE_SIZE,
             delta_time: 0,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration: Duration::from_secs(0),
         })
@@ -1,23 +1,29 @@
-use std::{time::{Instant, Duration}};
+use core::time::Duration;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "std")]
+use std::time::Instant;
 
-use crate::{events::{Event, FromBytes}, midi_error::MidiError, metadata::TimeDivision, metaevents::get_utf8_from_bytes};
+use alloc::{string::String, vec, vec::Vec};
+
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::{events::{require_len, Event, FromBytes}, midi_error::MidiError, metadata::TimeDivision, metaevents::get_utf8_from_bytes};
 
 use super::{calculate_time_duration, from_bytes_to_vlq, from_vlq_to_bytes, METAEVENT_BYTE};
 
 const METAEVENT_BYTE_TYPE: u8 = 0x06;
 
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct MarkerEvent {
     pub marker: String,
     text_size: u32,
     event_size: u8,
     delta_time: u32,
 
-    #[cfg_attr(feature = "json", serde(skip))]
+    #[cfg_attr(feature = "use_serde", serde(skip, default = "std::time::Instant::now"))]
+    #[cfg(feature = "std")]
     current_time: Instant,
     time_duration: Duration,
 }
@@ -47,6 +53,12 @@ impl Event for MarkerEvent {
         self.delta_time
     }
 
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        self.delta_time = delta_time;
+        self.time_duration = calculate_time_duration(delta_time, time_division, tempo);
+    }
+
+    #[cfg(feature = "std")]
     fn get_current_time(&self) -> Instant {
         self.current_time
     }
@@ -72,7 +84,7 @@ impl Event for MarkerEvent {
         Ok(bytes)
     }
 
-    fn as_any(&self) -> &dyn std::any::Any {
+    fn as_any(&self) -> &dyn core::any::Any {
         self
     }
 }
@@ -81,9 +93,11 @@ impl FromBytes for MarkerEvent {
     type Output = Self;
 
     fn from_bytes(data: &[u8], delta_time: u32, time_division: TimeDivision, tempo: u32) -> Result<Self::Output, MidiError> {
+        require_len(data, 2, "Marker")?;
+
         // Calculate the time duration
         let time_duration = calculate_time_duration(delta_time, time_division, tempo);
-        
+
         // The data starts after the 0xFF 0x01 bytes
         let data = &data[2..];
 
@@ -91,6 +105,7 @@ impl FromBytes for MarkerEvent {
         let (data_length, num_of_bytes) = from_bytes_to_vlq(&data);
 
         // Ensure we are only taking the data and not anything else past that incase we are given more bytes to follow
+        require_len(data, num_of_bytes as usize + data_length as usize, "Marker")?;
         let data = &data[(num_of_bytes as usize)..((num_of_bytes as u32 + data_length) as usize)];
 
         // Convert the bytes to UTF-8 text
@@ -168,4 +183,22 @@ mod text_event_tests {
         assert_eq!(text_event.get_event_size(), event_size);
     }
 
+    #[test]
+    fn create_marker_struct_from_bytes_uses_smpte_duration_when_time_division_is_smpte() {
+        let test_text = String::from("Chorus");
+        let test_text_len = from_vlq_to_bytes(test_text.len() as u32);
+
+        let mut bytes: Vec<u8> = vec![
+            METAEVENT_BYTE, METAEVENT_BYTE_TYPE
+        ];
+
+        bytes.extend_from_slice(&test_text_len);
+        bytes.extend_from_slice(test_text.as_bytes());
+
+        // -25fps, 40 ticks per frame: each tick is 1ms, independent of tempo.
+        let time_division = TimeDivision::SMPTE(25, 40);
+        let text_event = MarkerEvent::from_bytes(&bytes, 40, time_division, 120).unwrap();
+        assert_eq!(text_event.get_time_duration(), std::time::Duration::from_millis(40));
+    }
+
 }
\ No newline at end of file
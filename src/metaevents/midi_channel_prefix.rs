@@ -1,10 +1,15 @@
-use std::time::{Duration, Instant};
+use core::time::Duration;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use alloc::{string::String, vec, vec::Vec};
+
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
 
 use crate::{
-    events::{Event, FromBytes},
+    events::{require_len, Event, FromBytes},
     metadata::TimeDivision,
     midi_error::MidiError,
 };
@@ -16,13 +21,14 @@ const METAEVENT_BYTE_TYPE: u8 = 0x20;
 const MIDI_CHANNEL_PREFIX_SIZE: u8 = 4;
 
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct MidiChannelPrefixEvent {
     pub midi_channel: u8,
     event_size: u8,
     delta_time: u32,
 
-    #[cfg_attr(feature = "json", serde(skip))]
+    #[cfg_attr(feature = "use_serde", serde(skip, default = "std::time::Instant::now"))]
+    #[cfg(feature = "std")]
     current_time: Instant,
     time_duration: Duration,
 }
@@ -52,6 +58,12 @@ impl Event for MidiChannelPrefixEvent {
         self.delta_time
     }
 
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        self.delta_time = delta_time;
+        self.time_duration = calculate_time_duration(delta_time, time_division, tempo);
+    }
+
+    #[cfg(feature = "std")]
     fn get_current_time(&self) -> Instant {
         self.current_time
     }
@@ -75,7 +87,7 @@ impl Event for MidiChannelPrefixEvent {
         Ok(bytes)
     }
 
-    fn as_any(&self) -> &dyn std::any::Any {
+    fn as_any(&self) -> &dyn core::any::Any {
         self
     }
 }
@@ -89,6 +101,8 @@ impl FromBytes for MidiChannelPrefixEvent {
         time_division: TimeDivision,
         tempo: u32,
     ) -> Result<Self::Output, MidiError> {
+        require_len(data, 4, "Midi Channel Prefix")?;
+
         // Calculate the time duration
         let time_duration = calculate_time_duration(delta_time, time_division, tempo);
 
@@ -102,6 +116,7 @@ impl FromBytes for MidiChannelPrefixEvent {
             midi_channel,
             event_size: MIDI_CHANNEL_PREFIX_SIZE,
             delta_time,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration,
         })
@@ -114,6 +129,7 @@ impl MidiChannelPrefixEvent {
             midi_channel,
             event_size: MIDI_CHANNEL_PREFIX_SIZE,
             delta_time: 0,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration: Duration::from_secs(0),
         })
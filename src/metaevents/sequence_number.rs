@@ -1,9 +1,14 @@
-use std::time::{Duration, Instant};
+use core::time::Duration;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "std")]
+use std::time::Instant;
 
-use crate::events::{Event, FromBytes};
+use alloc::{string::String, vec, vec::Vec};
+
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::events::{require_len, Event, FromBytes};
 use crate::metadata::TimeDivision;
 use crate::midi_error::{MidiError, ParseError};
 
@@ -14,13 +19,14 @@ const METAEVENT_BYTE_TYPE: u8 = 0x20;
 const SEQUENCE_NUMBER_SIZE: u8 = 0x00;
 
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct SequenceNumber {
     pub sequence_number: u16,
     event_size: u8,
     delta_time: u32,
 
-    #[cfg_attr(feature = "json", serde(skip))]
+    #[cfg_attr(feature = "use_serde", serde(skip, default = "std::time::Instant::now"))]
+    #[cfg(feature = "std")]
     current_time: Instant,
     time_duration: Duration,
 }
@@ -46,6 +52,7 @@ impl Event for SequenceNumber {
         self.event_size
     }
 
+    #[cfg(feature = "std")]
     fn get_current_time(&self) -> Instant {
         self.current_time
     }
@@ -54,6 +61,11 @@ impl Event for SequenceNumber {
         self.delta_time
     }
 
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        self.delta_time = delta_time;
+        self.time_duration = calculate_time_duration(delta_time, time_division, tempo);
+    }
+
     fn get_time_duration(&self) -> Duration {
         self.time_duration
     }
@@ -74,7 +86,7 @@ impl Event for SequenceNumber {
         Ok(bytes)
     }
 
-    fn as_any(&self) -> &dyn std::any::Any {
+    fn as_any(&self) -> &dyn core::any::Any {
         self
     }
 }
@@ -89,11 +101,7 @@ impl FromBytes for SequenceNumber {
         tempo: u32,
     ) -> Result<Self, MidiError> {
         // Do some basic error checking
-        if data.len() != SEQUENCE_NUMBER_SIZE as usize {
-            return Err(MidiError::ParseError(ParseError::InvalidEventBytes(
-                String::from("Sequence Number Event Error: Invalid Size"),
-            )));
-        }
+        require_len(data, 5, "Sequence Number")?;
 
         if data[1] != 0x00 || data[2] != 0x02 {
             return Err(MidiError::ParseError(ParseError::InvalidEventBytes(
@@ -114,6 +122,7 @@ impl FromBytes for SequenceNumber {
             sequence_number,
             event_size: SEQUENCE_NUMBER_SIZE,
             delta_time,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration,
         })
@@ -126,6 +135,7 @@ impl SequenceNumber {
             sequence_number,
             event_size: SEQUENCE_NUMBER_SIZE,
             delta_time: 0,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration: Duration::from_secs(0),
         })
@@ -1,27 +1,35 @@
-use std::time::{Duration, Instant};
+use core::time::Duration;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use alloc::{string::String, vec, vec::Vec};
+
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
 
 use crate::{
-    events::{Event, FromBytes},
+    events::{require_len, Event, FromBytes},
     metadata::TimeDivision,
     midi_error::MidiError,
 };
 
+#[cfg(feature = "std")]
+use super::write_vlq;
 use super::{calculate_time_duration, from_bytes_to_vlq, from_vlq_to_bytes, METAEVENT_BYTE};
 
 const METAEVENT_BYTE_TYPE: u8 = 0x7F;
 
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct SequencerSpecificEvent {
     pub data: Vec<u8>,
     pub data_length: u32,
     event_size: u8,
     delta_time: u32,
 
-    #[cfg_attr(feature = "json", serde(skip))]
+    #[cfg_attr(feature = "use_serde", serde(skip, default = "std::time::Instant::now"))]
+    #[cfg(feature = "std")]
     current_time: Instant,
     time_duration: Duration,
 }
@@ -51,6 +59,12 @@ impl Event for SequencerSpecificEvent {
         self.delta_time
     }
 
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        self.delta_time = delta_time;
+        self.time_duration = calculate_time_duration(delta_time, time_division, tempo);
+    }
+
+    #[cfg(feature = "std")]
     fn get_current_time(&self) -> Instant {
         self.current_time
     }
@@ -76,7 +90,17 @@ impl Event for SequencerSpecificEvent {
         Ok(bytes)
     }
 
-    fn as_any(&self) -> &dyn std::any::Any {
+    #[cfg(feature = "std")]
+    fn write_to(&self, w: &mut dyn std::io::Write) -> Result<(), MidiError> {
+        write_vlq(w, self.delta_time)?;
+        w.write_all(&[METAEVENT_BYTE, METAEVENT_BYTE_TYPE])?;
+        write_vlq(w, self.data_length)?;
+        w.write_all(&self.data)?;
+
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
         self
     }
 }
@@ -90,6 +114,8 @@ impl FromBytes for SequencerSpecificEvent {
         time_division: TimeDivision,
         tempo: u32,
     ) -> Result<Self::Output, MidiError> {
+        require_len(data, 2, "Sequence Specific")?;
+
         // Calculate the time duration
         let time_duration = calculate_time_duration(delta_time, time_division, tempo);
 
@@ -101,6 +127,11 @@ impl FromBytes for SequencerSpecificEvent {
 
         // Grab the data
         // We don't care what the data actually is, we just need to extract it
+        require_len(
+            data,
+            num_of_bytes as usize + data_length as usize,
+            "Sequence Specific",
+        )?;
         let data = &data[(num_of_bytes as usize)..((num_of_bytes as u32 + data_length) as usize)];
 
         // The event size is 0xFF 0x7F bytes plus the number of bytes from the VLQ size + the length of the data
@@ -111,6 +142,7 @@ impl FromBytes for SequencerSpecificEvent {
             data_length,
             event_size,
             delta_time,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration,
         })
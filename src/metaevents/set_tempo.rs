@@ -1,10 +1,15 @@
-use std::time::{Duration, Instant};
+use core::time::Duration;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use alloc::{string::String, vec, vec::Vec};
+
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
 
 use crate::{
-    events::{Event, FromBytes},
+    events::{require_len, Event, FromBytes},
     metadata::TimeDivision,
     midi_error::MidiError,
 };
@@ -19,13 +24,14 @@ const METAEVENT_BYTE_TYPE: u8 = 0x51;
 const SET_TEMPO_SIZE: u8 = 6;
 
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct SetTempoEvent {
     pub tempo: f64,
     event_size: u8,
     delta_time: u32,
 
-    #[cfg_attr(feature = "json", serde(skip))]
+    #[cfg_attr(feature = "use_serde", serde(skip, default = "std::time::Instant::now"))]
+    #[cfg(feature = "std")]
     current_time: Instant,
     time_duration: Duration,
 }
@@ -55,6 +61,12 @@ impl Event for SetTempoEvent {
         self.delta_time
     }
 
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        self.delta_time = delta_time;
+        self.time_duration = calculate_time_duration(delta_time, time_division, tempo);
+    }
+
+    #[cfg(feature = "std")]
     fn get_current_time(&self) -> Instant {
         self.current_time
     }
@@ -90,7 +102,7 @@ impl Event for SetTempoEvent {
         Ok(bytes)
     }
 
-    fn as_any(&self) -> &dyn std::any::Any {
+    fn as_any(&self) -> &dyn core::any::Any {
         self
     }
 }
@@ -104,6 +116,8 @@ impl FromBytes for SetTempoEvent {
         time_division: TimeDivision,
         tempo: u32,
     ) -> Result<Self::Output, MidiError> {
+        require_len(data, 6, "Set Tempo")?;
+
         // Calculate time duration
         let time_duration = calculate_time_duration(delta_time, time_division, tempo);
 
@@ -122,6 +136,7 @@ impl FromBytes for SetTempoEvent {
             event_size: SET_TEMPO_SIZE,
             delta_time,
             time_duration,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
         })
     }
@@ -133,6 +148,7 @@ impl SetTempoEvent {
             tempo,
             event_size: SET_TEMPO_SIZE,
             delta_time: 0,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration: Duration::from_secs(0),
         })
@@ -1,12 +1,17 @@
-use std::time::{Duration, Instant};
+use core::time::Duration;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use alloc::{format, vec, vec::Vec, string::String};
+
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
 
 use crate::{
-    events::{Event, FromBytes},
+    events::{require_len, Event, FromBytes},
     metadata::TimeDivision,
-    midi_error::MidiError,
+    midi_error::{EventError, MidiError},
 };
 
 use super::{calculate_time_duration, from_vlq_to_bytes, METAEVENT_BYTE};
@@ -15,21 +20,62 @@ const METAEVENT_BYTE_TYPE: u8 = 0x54;
 
 const SMPTE_OFFSET_SIZE: u8 = 8;
 
+/// The frame-rate code packed into the top two bits of an SMPTE offset's `hr` byte.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
+pub enum SmpteFrameRate {
+    Fps24,
+    Fps25,
+    Fps30DropFrame,
+    Fps30NonDrop,
+}
+
+impl SmpteFrameRate {
+    fn to_bits(self) -> u8 {
+        match self {
+            SmpteFrameRate::Fps24 => 0b00,
+            SmpteFrameRate::Fps25 => 0b01,
+            SmpteFrameRate::Fps30DropFrame => 0b10,
+            SmpteFrameRate::Fps30NonDrop => 0b11,
+        }
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        match bits {
+            0b00 => SmpteFrameRate::Fps24,
+            0b01 => SmpteFrameRate::Fps25,
+            0b10 => SmpteFrameRate::Fps30DropFrame,
+            _ => SmpteFrameRate::Fps30NonDrop,
+        }
+    }
+
+    /// The highest frame number this rate allows (inclusive) within a single second.
+    fn max_frame(self) -> u8 {
+        match self {
+            SmpteFrameRate::Fps24 => 23,
+            SmpteFrameRate::Fps25 => 24,
+            SmpteFrameRate::Fps30DropFrame | SmpteFrameRate::Fps30NonDrop => 29,
+        }
+    }
+}
+
 /// Used to store the SMPTE Offset Event.
 /// This event is not really used in this library but it is being
 /// stored to keep the integrity of the MIDI file
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct SMPTEOffsetEvent {
     pub hour: u8,
     pub minute: u8,
     pub second: u8,
-    pub frame_rate: u8,
+    pub frame: u8,
+    pub frame_rate: SmpteFrameRate,
     pub fractional_frames: u8,
     event_size: u8,
     delta_time: u32,
 
-    #[cfg_attr(feature = "json", serde(skip))]
+    #[cfg_attr(feature = "use_serde", serde(skip, default = "std::time::Instant::now"))]
+    #[cfg(feature = "std")]
     current_time: Instant,
     time_duration: Duration,
 }
@@ -59,6 +105,12 @@ impl Event for SMPTEOffsetEvent {
         self.delta_time
     }
 
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        self.delta_time = delta_time;
+        self.time_duration = calculate_time_duration(delta_time, time_division, tempo);
+    }
+
+    #[cfg(feature = "std")]
     fn get_current_time(&self) -> Instant {
         self.current_time
     }
@@ -70,10 +122,10 @@ impl Event for SMPTEOffsetEvent {
     fn to_bytes(&self) -> Result<Vec<u8>, MidiError> {
         let mut bytes: Vec<u8> = vec![METAEVENT_BYTE, METAEVENT_BYTE_TYPE, (SMPTE_OFFSET_SIZE - 3)];
         bytes.extend_from_slice(&[
-            self.hour,
+            (self.frame_rate.to_bits() << 5) | self.hour,
             self.minute,
             self.second,
-            self.frame_rate,
+            self.frame,
             self.fractional_frames,
         ]);
         Ok(bytes)
@@ -81,18 +133,11 @@ impl Event for SMPTEOffsetEvent {
 
     fn to_bytes_delta_time(&self) -> Result<Vec<u8>, MidiError> {
         let mut bytes: Vec<u8> = from_vlq_to_bytes(self.delta_time);
-        bytes.extend_from_slice(&[METAEVENT_BYTE, METAEVENT_BYTE_TYPE, (SMPTE_OFFSET_SIZE - 3)]);
-        bytes.extend_from_slice(&[
-            self.hour,
-            self.minute,
-            self.second,
-            self.frame_rate,
-            self.fractional_frames,
-        ]);
+        bytes.extend_from_slice(&self.to_bytes()?);
         Ok(bytes)
     }
 
-    fn as_any(&self) -> &dyn std::any::Any {
+    fn as_any(&self) -> &dyn core::any::Any {
         self
     }
 }
@@ -106,14 +151,18 @@ impl FromBytes for SMPTEOffsetEvent {
         time_division: TimeDivision,
         tempo: u32,
     ) -> Result<Self::Output, MidiError> {
+        require_len(data, 8, "SMPTE Offset")?;
+
         // Calculate the time duration
         let time_duration = calculate_time_duration(delta_time, time_division, tempo);
 
         // The data starts after 0xFF 0x54 0x05
         let data = &data[3..];
 
-        // The first byte is the hour
-        let hour = data[0];
+        // The top two bits of the `hr` byte are the frame-rate code; the low five bits are the
+        // real hour 0-23
+        let frame_rate = SmpteFrameRate::from_bits(data[0] >> 5);
+        let hour = data[0] & 0b0001_1111;
 
         // The next byte is the minute
         let minute = data[1];
@@ -121,22 +170,120 @@ impl FromBytes for SMPTEOffsetEvent {
         // The next byte is the second
         let second = data[2];
 
-        // The byte is the frame rate
-        let frame_rate = data[3];
+        // The next byte is the frame count
+        let frame = data[3];
 
         // The final byte is the fractional frame
         let fractional_frames = data[4];
 
+        if minute > 59 {
+            return Err(MidiError::EventError(EventError::InvalidSmpteOffset(
+                format!("{} is an invalid minute. It must be between 0 and 59", minute),
+            )));
+        }
+
+        if second > 59 {
+            return Err(MidiError::EventError(EventError::InvalidSmpteOffset(
+                format!("{} is an invalid second. It must be between 0 and 59", second),
+            )));
+        }
+
+        if frame > frame_rate.max_frame() {
+            return Err(MidiError::EventError(EventError::InvalidSmpteOffset(format!(
+                "{} is an invalid frame for {:?}. It must be between 0 and {}",
+                frame,
+                frame_rate,
+                frame_rate.max_frame()
+            ))));
+        }
+
+        if fractional_frames > 99 {
+            return Err(MidiError::EventError(EventError::InvalidSmpteOffset(
+                format!(
+                    "{} is an invalid fractional frame count. It must be between 0 and 99",
+                    fractional_frames
+                ),
+            )));
+        }
+
         Ok(Self {
             hour,
             minute,
             second,
+            frame,
             frame_rate,
             fractional_frames,
             event_size: SMPTE_OFFSET_SIZE,
             delta_time,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes_for(hr_byte: u8, minute: u8, second: u8, frame: u8, fractional_frames: u8) -> [u8; 8] {
+        [
+            METAEVENT_BYTE,
+            METAEVENT_BYTE_TYPE,
+            (SMPTE_OFFSET_SIZE - 3),
+            hr_byte,
+            minute,
+            second,
+            frame,
+            fractional_frames,
+        ]
+    }
+
+    #[test]
+    fn decodes_the_frame_rate_and_hour_out_of_the_packed_byte() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        // Top two bits 0b10 (30 fps drop-frame) packed with hour 13
+        let bytes = bytes_for(0b0100_1101, 30, 15, 10, 0);
+
+        let event = SMPTEOffsetEvent::from_bytes(&bytes, 0, division, 120).unwrap();
+        assert_eq!(event.frame_rate, SmpteFrameRate::Fps30DropFrame);
+        assert_eq!(event.hour, 13);
+        assert_eq!(event.minute, 30);
+        assert_eq!(event.second, 15);
+        assert_eq!(event.frame, 10);
+    }
+
+    #[test]
+    fn round_trips_through_to_bytes() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        let bytes = bytes_for(0b0000_1001, 59, 59, 23, 99);
+
+        let event = SMPTEOffsetEvent::from_bytes(&bytes, 0, division, 120).unwrap();
+        assert_eq!(event.to_bytes().unwrap(), bytes.to_vec());
+    }
+
+    #[test]
+    fn rejects_a_minute_out_of_range() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        let bytes = bytes_for(0, 60, 0, 0, 0);
+
+        assert!(SMPTEOffsetEvent::from_bytes(&bytes, 0, division, 120).is_err());
+    }
+
+    #[test]
+    fn rejects_a_frame_beyond_the_selected_rates_bound() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        // Frame rate bits 0b00 => 24 fps, whose max frame is 23
+        let bytes = bytes_for(0b0000_0000, 0, 0, 24, 0);
+
+        assert!(SMPTEOffsetEvent::from_bytes(&bytes, 0, division, 120).is_err());
+    }
+
+    #[test]
+    fn rejects_fractional_frames_out_of_range() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        let bytes = bytes_for(0, 0, 0, 0, 100);
+
+        assert!(SMPTEOffsetEvent::from_bytes(&bytes, 0, division, 120).is_err());
+    }
+}
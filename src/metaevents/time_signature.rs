@@ -1,12 +1,15 @@
-use std::{
-    time::{Duration, Instant},
-};
+use core::time::Duration;
+
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use alloc::{string::String, vec, vec::Vec};
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
 
 use crate::{
-    events::{Event, FromBytes},
+    events::{require_len, Event, FromBytes},
     metadata::TimeDivision,
     midi_error::MidiError,
 };
@@ -18,7 +21,7 @@ const METAEVENT_BYTE_TYPE: u8 = 0x58;
 const TIME_SIGNATURE_SIZE: u8 = 7;
 
 #[derive(Debug, Clone, Copy)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct TimeSignature {
     pub numerator: u8,
     pub denominator: u8,
@@ -34,7 +37,7 @@ impl TimeSignature {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct TimeSignatureEvent {
     pub numerator: u8,
     pub denominator: u8,
@@ -43,7 +46,8 @@ pub struct TimeSignatureEvent {
     event_size: u8,
     delta_time: u32,
 
-    #[cfg_attr(feature = "json", serde(skip))]
+    #[cfg_attr(feature = "use_serde", serde(skip, default = "std::time::Instant::now"))]
+    #[cfg(feature = "std")]
     current_time: Instant,
     time_duration: Duration,
 }
@@ -73,6 +77,12 @@ impl Event for TimeSignatureEvent {
         self.delta_time
     }
 
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        self.delta_time = delta_time;
+        self.time_duration = calculate_time_duration(delta_time, time_division, tempo);
+    }
+
+    #[cfg(feature = "std")]
     fn get_current_time(&self) -> Instant {
         self.current_time
     }
@@ -114,7 +124,7 @@ impl Event for TimeSignatureEvent {
         Ok(bytes)
     }
 
-    fn as_any(&self) -> &dyn std::any::Any {
+    fn as_any(&self) -> &dyn core::any::Any {
         self
     }
 }
@@ -128,6 +138,8 @@ impl FromBytes for TimeSignatureEvent {
         time_division: TimeDivision,
         tempo: u32,
     ) -> Result<Self::Output, MidiError> {
+        require_len(data, 7, "Time Signature")?;
+
         // Calculate the time duration
         let time_duration = calculate_time_duration(delta_time, time_division, tempo);
 
@@ -154,6 +166,7 @@ impl FromBytes for TimeSignatureEvent {
             num_of_32nd_notes_per_quarter,
             event_size: TIME_SIGNATURE_SIZE,
             delta_time,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration,
         })
@@ -169,6 +182,7 @@ impl TimeSignatureEvent {
             num_of_32nd_notes_per_quarter: 8,
             event_size: TIME_SIGNATURE_SIZE,
             delta_time: 0,
+            #[cfg(feature = "std")]
             current_time: Instant::now(),
             time_duration: Duration::from_secs(0),
         })
@@ -1,4 +1,6 @@
-use std::fmt;
+use core::fmt;
+
+use alloc::string::{FromUtf8Error, String};
 
 #[derive(Debug)]
 pub enum ParseError {
@@ -8,6 +10,15 @@ pub enum ParseError {
     InvalidFPS,
     InvalidEventBytes(String),
     NotImplemented(String),
+    /// Not enough bytes remained to parse an event.
+    UnexpectedEof {
+        event: String,
+        needed: usize,
+        got: usize,
+    },
+    /// An RMID (RIFF-wrapped SMF) container was malformed: a bad signature, a truncated chunk, or
+    /// a missing `data` sub-chunk.
+    InvalidRiffContainer(String),
 }
 
 impl fmt::Display for ParseError {
@@ -19,14 +30,27 @@ impl fmt::Display for ParseError {
             ParseError::InvalidFPS => write!(f, "Invalid FPS value. It must be -24, -25, -29, or -30"),
             ParseError::InvalidEventBytes(ref e) => write!(f, "{}", e),
             ParseError::NotImplemented(ref e) => write!(f, "Not Implemented: {}", e),
+            ParseError::UnexpectedEof { event, needed, got } => write!(
+                f,
+                "Unexpected end of data while parsing {}: needed at least {} byte(s), got {}",
+                event, needed, got
+            ),
+            ParseError::InvalidRiffContainer(ref e) => write!(f, "Invalid RIFF container: {}", e),
         }
     }
 }
 
+// `ParseError`'s variants carry plain `String` context rather than an inner error, so there's no
+// `source()` to report; the impl is still useful so callers can walk a `MidiError` chain via
+// `std::error::Error` without special-casing this type.
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
 #[derive(Debug)]
 pub enum EventError {
     InvalidEvent,
     InvalidKeySignature(String),
+    InvalidSmpteOffset(String),
 }
 
 impl fmt::Display for EventError {
@@ -34,13 +58,20 @@ impl fmt::Display for EventError {
         match self {
             EventError::InvalidEvent => write!(f, "Invalid event"),
             EventError::InvalidKeySignature(ref e) => write!(f, "Invalid Key Signature: {}", e),
+            EventError::InvalidSmpteOffset(ref e) => write!(f, "Invalid SMPTE Offset: {}", e),
         }
     }
 }
 
+// Same reasoning as `ParseError`'s impl above: no inner error to report, but the trait impl lets
+// `EventError` participate in a walkable error chain.
+#[cfg(feature = "std")]
+impl std::error::Error for EventError {}
+
 #[derive(Debug)]
 pub enum MidiError {
-    FromUtf8Error(std::string::FromUtf8Error),
+    FromUtf8Error(FromUtf8Error),
+    #[cfg(feature = "std")]
     IoError(std::io::Error),
     FileError(usize),
     ParseError(ParseError),
@@ -48,13 +79,25 @@ pub enum MidiError {
 }
 
 // Implement Error for MidiError
-impl std::error::Error for MidiError {}
+#[cfg(feature = "std")]
+impl std::error::Error for MidiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MidiError::FromUtf8Error(ref e) => Some(e),
+            MidiError::IoError(ref e) => Some(e),
+            MidiError::ParseError(ref e) => Some(e),
+            MidiError::EventError(ref e) => Some(e),
+            MidiError::FileError(_) => None,
+        }
+    }
+}
 
-impl std::fmt::Display for MidiError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl fmt::Display for MidiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             MidiError::FromUtf8Error(ref e) => write!(f, "Error Converting to UTF-8: {}", e),
             MidiError::FileError(ref e) => write!(f, "File error: {}", e),
+            #[cfg(feature = "std")]
             MidiError::IoError(ref e) => write!(f, "I/O error: {}", e),
             MidiError::ParseError(ref e) => write!(f, "Parse error: {}. Possible file corruption?", e),
             MidiError::EventError(ref e) => write!(f, "Event error: {}. Possible file corruption?", e),
@@ -62,6 +105,7 @@ impl std::fmt::Display for MidiError {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<std::io::Error> for MidiError {
     fn from(value: std::io::Error) -> Self {
         MidiError::IoError(value)
@@ -74,8 +118,8 @@ impl From<usize> for MidiError {
     }
 }
 
-impl From<std::string::FromUtf8Error> for MidiError {
-    fn from(value: std::string::FromUtf8Error) -> Self {
+impl From<FromUtf8Error> for MidiError {
+    fn from(value: FromUtf8Error) -> Self {
         MidiError::FromUtf8Error(value)
     }
 }
@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 use crate::metaevents::KeySignature;
 
@@ -33,7 +33,7 @@ pub struct Note<'a> {
 impl<'a> Note<'a> {
     pub fn new_from_midi_note_key_signature(midi_note: u8, key_signature: KeySignature) -> Self {
         let pitch_class_number = midi_note % 12;
-        let octave = ((midi_note / 12) - 2) as i8;
+        let octave = (midi_note as i16 / 12 - 2) as i8;
 
         let sharp_pitch_classes = [
             PitchClass::C, PitchClass::CSharp, PitchClass::D, PitchClass::DSharp, PitchClass::E,
@@ -64,7 +64,7 @@ impl<'a> Note<'a> {
 
     pub fn new_from_midi_note(midi_note: u8) -> Self {
         let pitch_class_number = midi_note % 12;
-        let octave = ((midi_note / 12) - 2) as i8;
+        let octave = (midi_note as i16 / 12 - 2) as i8;
 
         let sharp_pitch_classes = [
             PitchClass::C, PitchClass::CSharp, PitchClass::D, PitchClass::DSharp, PitchClass::E,
@@ -95,6 +95,72 @@ impl<'a> Note<'a> {
             PitchClass::B => 11,
         };
 
-        12_u8 * self.octave as u8 + base_number as u8
+        // Inverse of `new_from_midi_note`'s `octave = midi_note / 12 - 2`.
+        ((self.octave as i16 + 2) * 12 + base_number as i16) as u8
+    }
+
+    /// Transpose this note by `semitones` (positive moves up, negative moves down), clamping the
+    /// resulting MIDI note number to the valid 0..=127 range.
+    ///
+    /// The result is respelled from scratch via `new_from_midi_note`, so it always uses the sharp
+    /// spelling; use [`Note::transpose_with_key_signature`] to respell with flats where the key
+    /// calls for it.
+    pub fn transpose(&self, semitones: i8) -> Self {
+        let midi_note = (self.to_midi_note() as i16 + semitones as i16).clamp(0, 127) as u8;
+        Self::new_from_midi_note(midi_note)
+    }
+
+    /// Same as [`Note::transpose`], but respells the result as sharps or flats according to
+    /// `key_signature`'s `num_of_accidentals`.
+    pub fn transpose_with_key_signature(&self, semitones: i8, key_signature: KeySignature) -> Self {
+        let midi_note = (self.to_midi_note() as i16 + semitones as i16).clamp(0, 127) as u8;
+        Self::new_from_midi_note_key_signature(midi_note, key_signature)
+    }
+
+    /// The signed number of semitones from this note to `other`: positive if `other` is higher,
+    /// negative if lower.
+    pub fn interval_to(&self, other: &Note) -> i8 {
+        (other.to_midi_note() as i16 - self.to_midi_note() as i16) as i8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn midi_note_round_trips_through_note() {
+        for midi_note in 0..=127u8 {
+            let note = Note::new_from_midi_note(midi_note);
+            assert_eq!(note.to_midi_note(), midi_note);
+        }
+    }
+
+    #[test]
+    fn transpose_moves_by_semitones() {
+        let middle_c = Note::new_from_midi_note(60);
+        let up_a_fifth = middle_c.transpose(7);
+        assert_eq!(up_a_fifth.to_midi_note(), 67);
+
+        let down_an_octave = middle_c.transpose(-12);
+        assert_eq!(down_an_octave.to_midi_note(), 48);
+    }
+
+    #[test]
+    fn transpose_clamps_to_valid_midi_range() {
+        let low_note = Note::new_from_midi_note(0);
+        assert_eq!(low_note.transpose(-12).to_midi_note(), 0);
+
+        let high_note = Note::new_from_midi_note(127);
+        assert_eq!(high_note.transpose(12).to_midi_note(), 127);
+    }
+
+    #[test]
+    fn interval_to_is_signed_semitone_distance() {
+        let middle_c = Note::new_from_midi_note(60);
+        let g_above = Note::new_from_midi_note(67);
+
+        assert_eq!(middle_c.interval_to(&g_above), 7);
+        assert_eq!(g_above.interval_to(&middle_c), -7);
     }
 }
\ No newline at end of file
@@ -0,0 +1,156 @@
+//! Merges every track's events into a single performance-order stream.
+//!
+//! Each `Track` only holds its own delta-time stream, so there's no way to walk a multi-track
+//! `Midi` in the order its events actually fire without first converting every track's deltas to
+//! absolute ticks and merging them. [`EventsInOrder`] does a k-way merge across tracks: it keeps
+//! one cursor per track (each already converted to `(absolute_tick, &EventKind)` pairs) and
+//! repeatedly yields whichever cursor's next tick is smallest, breaking ties by track index so
+//! meta/tempo events conventionally stored on track 0 fire before same-tick events on later
+//! tracks.
+
+use std::{iter::Peekable, time::Duration, vec::IntoIter};
+
+use crate::{events::Event, events::EventKind, tempo_map::TempoMap, track::Track};
+
+/// One event from a merged multi-track stream, annotated with where and when it fires.
+#[derive(Debug)]
+pub struct OrderedEvent<'a> {
+    /// The absolute tick position, measured from the start of the file.
+    pub tick: u32,
+    /// The index into the `Midi`'s track list this event came from.
+    pub track_index: usize,
+    /// The wall-clock duration from the start of the file to this event, per the shared
+    /// [`TempoMap`].
+    pub duration: Duration,
+    /// The event itself.
+    pub event: &'a EventKind,
+}
+
+type TrackTicks<'a> = Peekable<IntoIter<(u32, &'a EventKind)>>;
+
+/// A k-way merge iterator over every track's events, in performance order.
+///
+/// Built by `Midi::events_in_order`.
+pub struct EventsInOrder<'a> {
+    cursors: Vec<TrackTicks<'a>>,
+    tempo_map: TempoMap,
+}
+
+impl<'a> EventsInOrder<'a> {
+    /// Build a merged, time-ordered view over `tracks`.
+    ///
+    /// ### Arguments
+    /// * `tracks` The tracks to merge, in their original order
+    /// * `tempo_map` The tempo map to use when converting a merged tick to a `Duration`; by SMF
+    ///   convention this is built from track 0's `SetTempoEvent`s
+    ///
+    /// ### Returns
+    /// An iterator yielding every track's events interleaved in absolute-tick order
+    pub fn new(tracks: &'a [Track], tempo_map: TempoMap) -> Self {
+        let cursors = tracks
+            .iter()
+            .map(|track| {
+                let mut tick = 0u32;
+                let ticks: Vec<(u32, &'a EventKind)> = track
+                    .events
+                    .iter()
+                    .map(|serializable_event| {
+                        tick += serializable_event.0.get_delta_time();
+                        (tick, &serializable_event.0)
+                    })
+                    .collect();
+
+                ticks.into_iter().peekable()
+            })
+            .collect();
+
+        Self { cursors, tempo_map }
+    }
+}
+
+impl<'a> Iterator for EventsInOrder<'a> {
+    type Item = OrderedEvent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut next_track: Option<(usize, u32)> = None;
+
+        for (track_index, cursor) in self.cursors.iter_mut().enumerate() {
+            if let Some(&(tick, _)) = cursor.peek() {
+                let is_earlier = match next_track {
+                    Some((_, best_tick)) => tick < best_tick,
+                    None => true,
+                };
+                if is_earlier {
+                    next_track = Some((track_index, tick));
+                }
+            }
+        }
+
+        let (track_index, tick) = next_track?;
+        let (_, event) = self.cursors[track_index].next().expect("peeked cursor must have a next item");
+        let duration = self.tempo_map.ticks_to_duration(tick);
+
+        Some(OrderedEvent {
+            tick,
+            track_index,
+            duration,
+            event,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{events::FromBytes, messages::NoteOnEvent, metadata::TimeDivision};
+
+    fn track_with(events: Vec<EventKind>) -> Track {
+        Track {
+            events: events.into_iter().map(crate::events::SerializableEvent).collect(),
+            track_size: 0,
+        }
+    }
+
+    #[test]
+    fn interleaves_tracks_by_absolute_tick() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+
+        let track_a_note = NoteOnEvent::from_bytes(&[0x90, 60, 100], 0, division, 120).unwrap();
+        let track_a = track_with(vec![EventKind::NoteOn(track_a_note)]);
+
+        let track_b_note = NoteOnEvent::from_bytes(&[0x91, 64, 100], 0, division, 120).unwrap();
+        let track_b = track_with(vec![EventKind::NoteOn(track_b_note)]);
+
+        let tracks = vec![track_a, track_b];
+        let tempo_map = TempoMap::new(&tracks[0].events, division, 120);
+
+        let merged: Vec<_> = EventsInOrder::new(&tracks, tempo_map).collect();
+
+        assert_eq!(merged.len(), 2);
+        // Both events land on tick 0; ties break toward the lower track index.
+        assert_eq!(merged[0].track_index, 0);
+        assert_eq!(merged[1].track_index, 1);
+    }
+
+    #[test]
+    fn yields_events_in_absolute_tick_order_across_tracks() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+
+        let early_note = NoteOnEvent::from_bytes(&[0x90, 60, 100], 10, division, 120).unwrap();
+        let track_a = track_with(vec![EventKind::NoteOn(early_note)]);
+
+        let late_note = NoteOnEvent::from_bytes(&[0x91, 64, 100], 5, division, 120).unwrap();
+        let track_b = track_with(vec![EventKind::NoteOn(late_note)]);
+
+        let tracks = vec![track_a, track_b];
+        let tempo_map = TempoMap::new(&tracks[0].events, division, 120);
+
+        let merged: Vec<_> = EventsInOrder::new(&tracks, tempo_map).collect();
+
+        // Track B's note lands at tick 5, before track A's note at tick 10.
+        assert_eq!(merged[0].track_index, 1);
+        assert_eq!(merged[0].tick, 5);
+        assert_eq!(merged[1].track_index, 0);
+        assert_eq!(merged[1].tick, 10);
+    }
+}
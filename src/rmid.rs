@@ -0,0 +1,176 @@
+//! Read/write support for RMID containers: an RMID file is a RIFF container (form type `RMID`)
+//! whose `data` sub-chunk holds a standard `MThd`/`MTrk` byte stream, the same bytes a bare `.mid`
+//! file contains. DAWs and OS media libraries sometimes wrap MIDI this way, alongside optional
+//! sibling chunks (`INFO`, `DLS `) that this module skips rather than interprets.
+//!
+//! [`unwrap_rmid`] detects the `RIFF`...`RMID` signature and hands back the inner SMF payload so
+//! it can be fed straight to the existing parsers ([`crate::metadata::MetaData::new`],
+//! [`crate::track::Track::get_track_list`]); [`wrap_rmid`] does the reverse, wrapping an emitted
+//! SMF byte stream back into a minimal valid RIFF container.
+
+use alloc::{vec, vec::Vec};
+
+use crate::midi_error::{MidiError, ParseError};
+
+const RIFF_ID: [u8; 4] = *b"RIFF";
+const RMID_FORM_TYPE: [u8; 4] = *b"RMID";
+const DATA_CHUNK_ID: [u8; 4] = *b"data";
+
+/// The number of bytes before the first sibling chunk: the `RIFF` id, the 4-byte chunk size, and
+/// the `RMID` form type.
+const RIFF_HEADER_LEN: usize = 12;
+
+/// Returns `true` if `data` starts with the `RIFF`...`RMID` signature.
+///
+/// ### Arguments
+/// * `data` The bytes to check
+///
+/// ### Returns
+/// Whether `data` looks like an RMID container
+pub fn is_rmid(data: &[u8]) -> bool {
+    data.len() >= RIFF_HEADER_LEN && data[0..4] == RIFF_ID && data[8..12] == RMID_FORM_TYPE
+}
+
+/// Unwrap an RMID container, returning the inner SMF (`MThd`/`MTrk`) payload carried by its
+/// `data` sub-chunk.
+///
+/// ### Arguments
+/// * `data` The full RMID file contents
+///
+/// ### Returns
+/// The inner SMF byte stream, ready to hand to [`crate::metadata::MetaData::new`] /
+/// [`crate::track::Track::get_track_list`]
+pub fn unwrap_rmid(data: &[u8]) -> Result<&[u8], MidiError> {
+    if !is_rmid(data) {
+        return Err(MidiError::ParseError(ParseError::InvalidRiffContainer(
+            alloc::string::String::from("Missing RIFF...RMID signature"),
+        )));
+    }
+
+    let riff_size = u32::from_le_bytes([data[4], data[5], data[6], data[7]]) as usize;
+    let riff_end = (RIFF_HEADER_LEN - 4 + riff_size).min(data.len());
+
+    let mut position = RIFF_HEADER_LEN;
+    while position + 8 <= riff_end {
+        let chunk_id = &data[position..position + 4];
+        let chunk_size =
+            u32::from_le_bytes([data[position + 4], data[position + 5], data[position + 6], data[position + 7]])
+                as usize;
+        let chunk_data_start = position + 8;
+        let chunk_data_end = chunk_data_start + chunk_size;
+
+        if chunk_data_end > data.len() {
+            return Err(MidiError::ParseError(ParseError::InvalidRiffContainer(
+                alloc::string::String::from("Chunk size runs past the end of the file"),
+            )));
+        }
+
+        if chunk_id == DATA_CHUNK_ID {
+            return Ok(&data[chunk_data_start..chunk_data_end]);
+        }
+
+        // Sibling chunks (INFO, DLS, etc.) are skipped; every chunk is padded to an even length.
+        position = chunk_data_end + (chunk_size % 2);
+    }
+
+    Err(MidiError::ParseError(ParseError::InvalidRiffContainer(
+        alloc::string::String::from("No data sub-chunk found"),
+    )))
+}
+
+/// Wrap an emitted SMF byte stream (an `MThd` header followed by its `MTrk` chunks) in a minimal
+/// valid RMID container holding a single `data` sub-chunk.
+///
+/// ### Arguments
+/// * `smf_bytes` The serialized SMF bytes, e.g. the output of assembling `Midi::to_bytes`
+///
+/// ### Returns
+/// The full RMID file contents
+pub fn wrap_rmid(smf_bytes: &[u8]) -> Vec<u8> {
+    let padded_data_len = smf_bytes.len() + (smf_bytes.len() % 2);
+
+    // The RIFF size covers everything after the first 8 bytes: the `RMID` form type plus the
+    // `data` chunk's id, size field, payload, and padding.
+    let riff_size = 4 + 8 + padded_data_len;
+
+    let mut bytes = Vec::with_capacity(8 + riff_size);
+    bytes.extend_from_slice(&RIFF_ID);
+    bytes.extend_from_slice(&(riff_size as u32).to_le_bytes());
+    bytes.extend_from_slice(&RMID_FORM_TYPE);
+
+    bytes.extend_from_slice(&DATA_CHUNK_ID);
+    bytes.extend_from_slice(&(smf_bytes.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(smf_bytes);
+    if smf_bytes.len() % 2 != 0 {
+        bytes.push(0x00);
+    }
+
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_smf() -> Vec<u8> {
+        vec![b'M', b'T', b'h', b'd', 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x01, 0x00, 0x60]
+    }
+
+    #[test]
+    fn test_is_rmid_detects_signature() {
+        let wrapped = wrap_rmid(&sample_smf());
+        assert!(is_rmid(&wrapped));
+        assert!(!is_rmid(&sample_smf()));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let smf = sample_smf();
+        let wrapped = wrap_rmid(&smf);
+        let unwrapped = unwrap_rmid(&wrapped).unwrap();
+        assert_eq!(unwrapped, &smf[..]);
+    }
+
+    #[test]
+    fn test_round_trip_odd_length_payload_is_padded() {
+        let smf = vec![b'M', b'T', b'r', b'k', 0x00, 0x00, 0x00, 0x01, 0xAB];
+        let wrapped = wrap_rmid(&smf);
+        // The data chunk's payload length is odd, so the container must carry one pad byte.
+        assert_eq!(wrapped.len() % 2, 0);
+        let unwrapped = unwrap_rmid(&wrapped).unwrap();
+        assert_eq!(unwrapped, &smf[..]);
+    }
+
+    #[test]
+    fn test_unwrap_rmid_skips_sibling_chunks() {
+        let smf = sample_smf();
+        let mut data = Vec::new();
+        data.extend_from_slice(b"INFO");
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(b"IART");
+
+        let mut riff_body = Vec::new();
+        riff_body.extend_from_slice(&data);
+        riff_body.extend_from_slice(b"data");
+        riff_body.extend_from_slice(&(smf.len() as u32).to_le_bytes());
+        riff_body.extend_from_slice(&smf);
+
+        let mut file = Vec::new();
+        file.extend_from_slice(&RIFF_ID);
+        file.extend_from_slice(&((4 + riff_body.len()) as u32).to_le_bytes());
+        file.extend_from_slice(&RMID_FORM_TYPE);
+        file.extend_from_slice(&riff_body);
+
+        let unwrapped = unwrap_rmid(&file).unwrap();
+        assert_eq!(unwrapped, &smf[..]);
+    }
+
+    #[test]
+    fn test_unwrap_rmid_rejects_non_riff_data() {
+        let result = unwrap_rmid(&sample_smf());
+        assert!(matches!(
+            result,
+            Err(MidiError::ParseError(ParseError::InvalidRiffContainer(_)))
+        ));
+    }
+}
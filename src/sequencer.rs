@@ -0,0 +1,160 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    events::Event,
+    metadata::TimeDivision,
+    tempo_map::TempoMap,
+    track::Track,
+};
+
+/// A single event, waiting to fire at a scheduled offset from playback start.
+struct ScheduledEvent {
+    fire_at: Duration,
+    bytes: Vec<u8>,
+}
+
+/// Plays back parsed tracks in real time.
+///
+/// Every event already carries a `time_duration`, but it was computed from the tempo in effect
+/// when the file was parsed, so it goes stale the moment a Set Tempo meta event fires partway
+/// through. Building a `Sequencer` builds a [`TempoMap`] per track and uses it to recompute every
+/// event's firing time from the tempo actually in effect at that point rather than trusting the
+/// stored value.
+pub struct Sequencer {
+    schedule: Vec<ScheduledEvent>,
+    cursor: usize,
+    start: Instant,
+}
+
+impl Sequencer {
+    /// Build a playback schedule from a file's parsed tracks.
+    ///
+    /// ### Arguments
+    /// * `tracks` The file's parsed tracks, in order
+    /// * `time_division` The file's time division, used to convert ticks to durations
+    /// * `initial_tempo` The tempo in effect before any Set Tempo event is seen
+    ///
+    /// ### Returns
+    /// A `Sequencer` ready to `poll()`, with playback starting now
+    pub fn new(tracks: &[Track], time_division: TimeDivision, initial_tempo: u32) -> Self {
+        let mut schedule = Vec::new();
+
+        for track in tracks {
+            let tempo_map = TempoMap::new(&track.events, time_division, initial_tempo);
+            let mut tick = 0;
+
+            for serializable_event in &track.events {
+                let event = &serializable_event.0;
+                tick += event.get_delta_time();
+                let fire_at = tempo_map.ticks_to_duration(tick);
+
+                if let Ok(bytes) = event.to_bytes() {
+                    schedule.push(ScheduledEvent { fire_at, bytes });
+                }
+            }
+        }
+
+        schedule.sort_by(|a, b| a.fire_at.cmp(&b.fire_at));
+
+        Self {
+            schedule,
+            cursor: 0,
+            start: Instant::now(),
+        }
+    }
+
+    /// Poll for events whose scheduled time has arrived, returning each due event's `to_bytes()`
+    /// payload in firing order so a caller can pump them straight to a MIDI output device.
+    ///
+    /// ### Arguments
+    /// * `now` The current time, normally `Instant::now()`
+    ///
+    /// ### Returns
+    /// The payloads of every event that has come due since the last `poll`
+    pub fn poll(&mut self, now: Instant) -> Vec<Vec<u8>> {
+        let elapsed = now.saturating_duration_since(self.start);
+        let mut due = Vec::new();
+
+        while self.cursor < self.schedule.len() && self.schedule[self.cursor].fire_at <= elapsed {
+            due.push(self.schedule[self.cursor].bytes.clone());
+            self.cursor += 1;
+        }
+
+        due
+    }
+
+    /// Pause or seek playback by rebasing the reference instant that elapsed time is measured
+    /// from, moving the read cursor to match without recomputing the schedule.
+    ///
+    /// ### Arguments
+    /// * `position` How far into the schedule playback should resume from
+    pub fn seek(&mut self, position: Duration) {
+        self.start = Instant::now() - position;
+        self.cursor = self
+            .schedule
+            .iter()
+            .position(|event| event.fire_at > position)
+            .unwrap_or(self.schedule.len());
+    }
+
+    /// Whether every scheduled event has already fired.
+    pub fn is_finished(&self) -> bool {
+        self.cursor >= self.schedule.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        events::{EventKind, FromBytes},
+        messages::NoteOnEvent,
+        metaevents::EndOfTrack,
+    };
+
+    fn track_with(events: Vec<EventKind>) -> Track {
+        let track_size = 0;
+        Track {
+            events: events.into_iter().map(crate::events::SerializableEvent).collect(),
+            track_size,
+        }
+    }
+
+    #[test]
+    fn test_new_schedules_events_in_order() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        let note_a =
+            NoteOnEvent::from_bytes(&[0x90, 60, 100], 0, division, 120).unwrap();
+        let note_b =
+            NoteOnEvent::from_bytes(&[0x90, 64, 100], 96, division, 120).unwrap();
+
+        let track = track_with(vec![EventKind::NoteOn(note_a), EventKind::NoteOn(note_b)]);
+        let sequencer = Sequencer::new(&[track], division, 120);
+
+        assert_eq!(sequencer.schedule.len(), 2);
+        assert!(sequencer.schedule[0].fire_at < sequencer.schedule[1].fire_at);
+    }
+
+    #[test]
+    fn test_poll_returns_only_due_events() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        let note_on = NoteOnEvent::from_bytes(&[0x90, 60, 100], 0, division, 120).unwrap();
+        let track = track_with(vec![EventKind::NoteOn(note_on)]);
+
+        let mut sequencer = Sequencer::new(&[track], division, 120);
+        let due = sequencer.poll(Instant::now());
+
+        assert_eq!(due.len(), 1);
+        assert!(sequencer.is_finished());
+        assert!(sequencer.poll(Instant::now()).is_empty());
+    }
+
+    #[test]
+    fn test_meta_event_is_scheduled_alongside_channel_voice_events() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        let track = track_with(vec![EventKind::EndOfTrack(EndOfTrack::new().unwrap())]);
+
+        let sequencer = Sequencer::new(&[track], division, 120);
+        assert_eq!(sequencer.schedule.len(), 1);
+    }
+}
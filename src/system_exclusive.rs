@@ -0,0 +1,243 @@
+//! System Exclusive events as they appear inside an `MTrk` chunk.
+//!
+//! Unlike a meta event, a file-based SysEx event has no `0xFF` prefix: it's just a status byte
+//! (`0xF0` for a normal SysEx message, or `0xF7` for an escape/continuation packet) followed by a
+//! VLQ length and that many data bytes, identical in shape to [`SequencerSpecificEvent`](crate::metaevents::SequencerSpecificEvent).
+//! Both status bytes cancel running status, same as a meta event.
+
+use core::time::Duration;
+
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use alloc::{string::String, vec, vec::Vec};
+
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::{
+    events::{require_len, Event, FromBytes},
+    metadata::TimeDivision,
+    metaevents::{calculate_time_duration, from_bytes_to_vlq, from_vlq_to_bytes},
+    midi_error::MidiError,
+};
+
+#[cfg(feature = "std")]
+use crate::metaevents::write_vlq;
+
+/// A normal SysEx message (`0xF0`), complete in this event.
+pub const SYSTEM_EXCLUSIVE_BYTE: u8 = 0xF0;
+/// An escape/continuation SysEx packet (`0xF7`), used either to split a long SysEx message across
+/// packets or to smuggle arbitrary bytes onto the wire outside of SysEx.
+pub const SYSTEM_EXCLUSIVE_ESCAPE_BYTE: u8 = 0xF7;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
+pub struct SystemExclusiveEvent {
+    pub status: u8,
+    pub data: Vec<u8>,
+    data_length: u32,
+    event_size: u32,
+    delta_time: u32,
+
+    #[cfg_attr(feature = "use_serde", serde(skip, default = "std::time::Instant::now"))]
+    #[cfg(feature = "std")]
+    current_time: Instant,
+    time_duration: Duration,
+}
+
+impl Event for SystemExclusiveEvent {
+    fn get_event_name(&self) -> String {
+        String::from("System Exclusive")
+    }
+
+    fn is_running_status_allowed(&self) -> bool {
+        false
+    }
+
+    fn event_type(&self) -> u8 {
+        self.status
+    }
+
+    fn get_channel(&self) -> u8 {
+        0
+    }
+
+    fn get_event_size(&self) -> u8 {
+        self.event_size.min(u8::MAX as u32) as u8
+    }
+
+    fn get_delta_time(&self) -> u32 {
+        self.delta_time
+    }
+
+    fn set_delta_time(&mut self, delta_time: u32, time_division: TimeDivision, tempo: u32) {
+        self.delta_time = delta_time;
+        self.time_duration = calculate_time_duration(delta_time, time_division, tempo);
+    }
+
+    #[cfg(feature = "std")]
+    fn get_current_time(&self) -> Instant {
+        self.current_time
+    }
+
+    fn get_time_duration(&self) -> Duration {
+        self.time_duration
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, MidiError> {
+        let mut bytes: Vec<u8> = vec![self.status];
+        bytes.extend_from_slice(&from_vlq_to_bytes(self.data_length));
+        bytes.extend_from_slice(&self.data);
+
+        Ok(bytes)
+    }
+
+    fn to_bytes_delta_time(&self) -> Result<Vec<u8>, MidiError> {
+        let mut bytes: Vec<u8> = from_vlq_to_bytes(self.delta_time);
+        bytes.extend_from_slice(&self.to_bytes()?);
+
+        Ok(bytes)
+    }
+
+    #[cfg(feature = "std")]
+    fn write_to(&self, w: &mut dyn std::io::Write) -> Result<(), MidiError> {
+        write_vlq(w, self.delta_time)?;
+        w.write_all(&[self.status])?;
+        write_vlq(w, self.data_length)?;
+        w.write_all(&self.data)?;
+
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+impl SystemExclusiveEvent {
+    /// Parse a SysEx event, given which status byte (`0xF0` or `0xF7`) introduced it.
+    ///
+    /// # Arguments
+    /// * `data` The bytes to parse, starting with the VLQ length that follows the status byte
+    /// * `status` The status byte that was already consumed (`0xF0` or `0xF7`)
+    /// * `delta_time` The delta time already read for this event
+    /// * `time_division` The file's time division
+    /// * `tempo` The tempo in effect for this event
+    pub fn new_from_status(
+        data: &[u8],
+        status: u8,
+        delta_time: u32,
+        time_division: TimeDivision,
+        tempo: u32,
+    ) -> Result<Self, MidiError> {
+        require_len(data, 1, "System Exclusive")?;
+
+        let time_duration = calculate_time_duration(delta_time, time_division, tempo);
+
+        let (data_length, num_of_bytes) = from_bytes_to_vlq(data);
+
+        require_len(
+            data,
+            num_of_bytes as usize + data_length as usize,
+            "System Exclusive",
+        )?;
+        let payload = &data[(num_of_bytes as usize)..((num_of_bytes as u32 + data_length) as usize)];
+
+        // The event size is the status byte plus the VLQ length bytes plus the payload. Kept as a
+        // `u32` since manufacturer SysEx dumps routinely exceed 255 bytes; see `total_size`.
+        let event_size = 1 + num_of_bytes as u32 + payload.len() as u32;
+
+        Ok(Self {
+            status,
+            data: payload.to_vec(),
+            data_length,
+            event_size,
+            delta_time,
+            #[cfg(feature = "std")]
+            current_time: Instant::now(),
+            time_duration,
+        })
+    }
+
+    /// The full number of bytes this event consumes, including the status byte, the VLQ length,
+    /// and the payload.
+    ///
+    /// Unlike [`Event::get_event_size`], which is capped to `u8` by the shared trait, this isn't
+    /// truncated, so callers advancing a parse position past a SysEx event (e.g. `Track::new`)
+    /// should use this instead when the payload may be at or beyond 253 bytes.
+    pub fn total_size(&self) -> u32 {
+        self.event_size
+    }
+}
+
+impl FromBytes for SystemExclusiveEvent {
+    type Output = Self;
+
+    fn from_bytes(
+        data: &[u8],
+        delta_time: u32,
+        time_division: TimeDivision,
+        tempo: u32,
+    ) -> Result<Self::Output, MidiError> {
+        require_len(data, 1, "System Exclusive")?;
+
+        Self::new_from_status(&data[1..], data[0], delta_time, time_division, tempo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_a_complete_sysex_message() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        let payload = [0x41, 0x10, 0x42, 0x12, 0xF7];
+        let mut bytes = vec![SYSTEM_EXCLUSIVE_BYTE];
+        bytes.extend_from_slice(&from_vlq_to_bytes(payload.len() as u32));
+        bytes.extend_from_slice(&payload);
+
+        let event = SystemExclusiveEvent::from_bytes(&bytes, 0, division, 120).unwrap();
+        assert_eq!(event.status, SYSTEM_EXCLUSIVE_BYTE);
+        assert_eq!(event.data, payload.to_vec());
+        assert_eq!(event.get_event_size() as usize, bytes.len());
+        assert_eq!(event.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn parses_an_escape_packet() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        let payload = [0x01, 0x02, 0x03];
+        let mut bytes = vec![SYSTEM_EXCLUSIVE_ESCAPE_BYTE];
+        bytes.extend_from_slice(&from_vlq_to_bytes(payload.len() as u32));
+        bytes.extend_from_slice(&payload);
+
+        let event = SystemExclusiveEvent::from_bytes(&bytes, 0, division, 120).unwrap();
+        assert_eq!(event.status, SYSTEM_EXCLUSIVE_ESCAPE_BYTE);
+        assert_eq!(event.data, payload.to_vec());
+    }
+
+    #[test]
+    fn total_size_does_not_truncate_a_payload_beyond_u8_range() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        let payload = vec![0x10u8; 300];
+        let mut bytes = vec![SYSTEM_EXCLUSIVE_BYTE];
+        bytes.extend_from_slice(&from_vlq_to_bytes(payload.len() as u32));
+        bytes.extend_from_slice(&payload);
+
+        let event = SystemExclusiveEvent::from_bytes(&bytes, 0, division, 120).unwrap();
+        assert_eq!(event.total_size() as usize, bytes.len());
+        // The shared `Event::get_event_size` is stuck at `u8`, so it caps out instead of wrapping.
+        assert_eq!(event.get_event_size(), u8::MAX);
+    }
+
+    #[test]
+    fn is_not_allowed_to_use_running_status() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        let bytes = [SYSTEM_EXCLUSIVE_BYTE, 0x00];
+
+        let event = SystemExclusiveEvent::from_bytes(&bytes, 0, division, 120).unwrap();
+        assert!(!event.is_running_status_allowed());
+    }
+}
@@ -0,0 +1,245 @@
+//! Tick-to-wall-clock-time conversion that accounts for tempo changes within a track.
+//!
+//! Each event only carries the tempo in effect when it was parsed, so converting a tick position
+//! to absolute time using the current tempo alone produces wrong results for every tick that
+//! comes after a `SetTempoEvent`. `TempoMap` scans a track's events once, recording every tempo
+//! change as a `(tick, microseconds_per_quarter)` breakpoint, then walks those breakpoints to
+//! convert between tick positions and durations, accumulating piecewise across whatever tempo
+//! was in effect at each point.
+
+use core::time::Duration;
+
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    events::{Event, EventKind, SerializableEvent},
+    metadata::TimeDivision,
+    metaevents::bpm_to_microseconds,
+};
+
+/// A tempo change recorded at the tick position it takes effect.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TempoSegment {
+    start_tick: u32,
+    micros_per_quarter: u32,
+}
+
+/// Converts between tick positions and wall-clock durations, honoring every tempo change in a
+/// track.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TempoMap {
+    time_division: TimeDivision,
+    segments: Vec<TempoSegment>,
+}
+
+impl TempoMap {
+    /// Build a tempo map by scanning a track's events for `SetTempoEvent`s.
+    ///
+    /// ### Arguments
+    /// * `events` The track's events, in order
+    /// * `time_division` The file's time division
+    /// * `initial_tempo` The tempo in effect before any `SetTempoEvent` is seen, in BPM
+    ///
+    /// ### Returns
+    /// A `TempoMap` ready to convert between ticks and durations
+    pub fn new(
+        events: &[SerializableEvent],
+        time_division: TimeDivision,
+        initial_tempo: u32,
+    ) -> Self {
+        let mut segments = vec![TempoSegment {
+            start_tick: 0,
+            micros_per_quarter: bpm_to_microseconds(initial_tempo as f64),
+        }];
+
+        let mut tick: u32 = 0;
+        for event in events {
+            tick += event.0.get_delta_time();
+
+            if let EventKind::SetTempo(set_tempo) = &event.0 {
+                segments.push(TempoSegment {
+                    start_tick: tick,
+                    micros_per_quarter: bpm_to_microseconds(set_tempo.tempo),
+                });
+            }
+        }
+
+        Self {
+            time_division,
+            segments,
+        }
+    }
+
+    /// Convert a tick position to the wall-clock duration elapsed since tick 0.
+    ///
+    /// ### Arguments
+    /// * `tick` The tick position to convert
+    ///
+    /// ### Returns
+    /// The duration elapsed from tick 0 to `tick`, across every tempo change along the way
+    pub fn ticks_to_duration(&self, tick: u32) -> Duration {
+        self.tick_to_duration(tick as u64)
+    }
+
+    /// Same as [`TempoMap::ticks_to_duration`], but takes `tick` as a `u64` for callers (e.g. a
+    /// merged multi-track timeline) whose absolute tick position can exceed `u32`.
+    ///
+    /// The walk itself is done natively in `u64` (a `TempoSegment`'s `start_tick` is widened as
+    /// it's read), so this doesn't just alias the `u32` version with a cast — it's the other way
+    /// around.
+    ///
+    /// ### Arguments
+    /// * `tick` The tick position to convert
+    ///
+    /// ### Returns
+    /// The duration elapsed from tick 0 to `tick`
+    pub fn tick_to_duration(&self, tick: u64) -> Duration {
+        match self.time_division {
+            // SMPTE ticks are a fixed wall-clock duration, independent of tempo.
+            TimeDivision::SMPTE(fps, ticks_per_frame) => {
+                Duration::from_secs_f64(tick as f64 * seconds_per_smpte_tick(fps, ticks_per_frame))
+            }
+            TimeDivision::PulsesPerQuarterNote(ppqn) => {
+                let ppqn = ppqn as f64;
+                let mut elapsed_micros = 0.0_f64;
+
+                for (index, segment) in self.segments.iter().enumerate() {
+                    let start_tick = segment.start_tick as u64;
+                    if start_tick >= tick {
+                        break;
+                    }
+
+                    let segment_end = self
+                        .segments
+                        .get(index + 1)
+                        .map(|next| next.start_tick as u64)
+                        .unwrap_or(tick)
+                        .min(tick);
+
+                    let segment_ticks = (segment_end - start_tick) as f64;
+                    elapsed_micros += (segment_ticks / ppqn) * segment.micros_per_quarter as f64;
+                }
+
+                Duration::from_secs_f64(elapsed_micros / 1_000_000.0)
+            }
+        }
+    }
+
+    /// Convert a wall-clock duration back to the tick position it corresponds to.
+    ///
+    /// ### Arguments
+    /// * `duration` The duration elapsed since tick 0
+    ///
+    /// ### Returns
+    /// The tick position `duration` falls at, across every tempo change along the way
+    pub fn duration_to_ticks(&self, duration: Duration) -> u32 {
+        self.duration_to_tick(duration) as u32
+    }
+
+    /// Same as [`TempoMap::duration_to_ticks`], but returns a `u64` to mirror
+    /// [`TempoMap::tick_to_duration`].
+    ///
+    /// The walk itself is done natively in `u64`, so a target tick beyond `u32::MAX` (e.g. from a
+    /// very long merged multi-track timeline) doesn't wrap before `duration_to_ticks` truncates it
+    /// back down.
+    ///
+    /// ### Arguments
+    /// * `duration` The duration elapsed since tick 0
+    ///
+    /// ### Returns
+    /// The tick position `duration` falls at
+    pub fn duration_to_tick(&self, duration: Duration) -> u64 {
+        match self.time_division {
+            TimeDivision::SMPTE(fps, ticks_per_frame) => {
+                (duration.as_secs_f64() / seconds_per_smpte_tick(fps, ticks_per_frame)).round()
+                    as u64
+            }
+            TimeDivision::PulsesPerQuarterNote(ppqn) => {
+                let ppqn = ppqn as f64;
+                let target_micros = duration.as_secs_f64() * 1_000_000.0;
+                let mut elapsed_micros = 0.0_f64;
+
+                for (index, segment) in self.segments.iter().enumerate() {
+                    let start_tick = segment.start_tick as u64;
+                    let micros_per_tick = segment.micros_per_quarter as f64 / ppqn;
+                    let next_start = self.segments.get(index + 1).map(|next| next.start_tick as u64);
+
+                    let segment_micros = match next_start {
+                        Some(next_start) => (next_start - start_tick) as f64 * micros_per_tick,
+                        None => f64::INFINITY,
+                    };
+
+                    if elapsed_micros + segment_micros >= target_micros {
+                        let remaining_ticks = (target_micros - elapsed_micros) / micros_per_tick;
+                        return start_tick + remaining_ticks.round() as u64;
+                    }
+
+                    elapsed_micros += segment_micros;
+                }
+
+                0
+            }
+        }
+    }
+}
+
+fn seconds_per_smpte_tick(fps: u8, ticks_per_frame: u8) -> f64 {
+    // -29fps actually means 29.97 drop-frame (30000/1001), matching `calculate_time_duration`.
+    let effective_fps = if fps == 29 { 30_000.0 / 1_001.0 } else { fps as f64 };
+    1.0 / (effective_fps * ticks_per_frame as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{events::FromBytes, metaevents::SetTempoEvent};
+
+    fn set_tempo_at(tick_offset: u32, tempo: f64, division: TimeDivision) -> SerializableEvent {
+        let micros = crate::metaevents::bpm_to_microseconds(tempo).to_be_bytes();
+        let data = [0xFF, 0x51, 0x03, micros[1], micros[2], micros[3]];
+        let event = SetTempoEvent::from_bytes(&data, tick_offset, division, 120).unwrap();
+        SerializableEvent(EventKind::SetTempo(event))
+    }
+
+    #[test]
+    fn ticks_to_duration_respects_tempo_change() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        // Tempo doubles from 120 to 240 BPM after 96 ticks (one quarter note).
+        let events = vec![set_tempo_at(96, 240.0, division)];
+        let tempo_map = TempoMap::new(&events, division, 120);
+
+        assert_eq!(tempo_map.ticks_to_duration(96), Duration::from_millis(500));
+        // The next quarter note is twice as fast at 240 BPM.
+        assert_eq!(tempo_map.ticks_to_duration(192), Duration::from_millis(750));
+    }
+
+    #[test]
+    fn duration_to_ticks_is_the_inverse_of_ticks_to_duration() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        let events = vec![set_tempo_at(96, 240.0, division)];
+        let tempo_map = TempoMap::new(&events, division, 120);
+
+        let duration = tempo_map.ticks_to_duration(192);
+        assert_eq!(tempo_map.duration_to_ticks(duration), 192);
+    }
+
+    #[test]
+    fn tick_to_duration_u64_alias_matches_u32_version() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        let events = vec![set_tempo_at(96, 240.0, division)];
+        let tempo_map = TempoMap::new(&events, division, 120);
+
+        let duration = tempo_map.tick_to_duration(192);
+        assert_eq!(duration, tempo_map.ticks_to_duration(192));
+        assert_eq!(tempo_map.duration_to_tick(duration), 192u64);
+    }
+
+    #[test]
+    fn smpte_conversion_ignores_tempo() {
+        let division = TimeDivision::SMPTE(25, 40);
+        let tempo_map = TempoMap::new(&[], division, 120);
+
+        assert_eq!(tempo_map.ticks_to_duration(40), Duration::from_millis(40));
+        assert_eq!(tempo_map.duration_to_ticks(Duration::from_millis(40)), 40);
+    }
+}
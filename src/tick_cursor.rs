@@ -0,0 +1,170 @@
+//! Tick-driven playback cursor.
+//!
+//! [`crate::sequencer::Sequencer`] is driven by wall-clock time (`Instant::now()`), which suits
+//! real-time playback but not a caller stepping through a file by tick count instead — e.g.
+//! following an external MIDI clock, or stepping a non-real-time renderer one tick at a time.
+//! `TickCursor` polls by accumulated tick count instead, with no notion of wall-clock time at all.
+
+use alloc::vec::Vec;
+
+use crate::{events::Event, events::EventKind, track::Track};
+
+/// One track's read position through [`TickCursor::poll`].
+struct TrackProgress {
+    /// Index of the next not-yet-fired event in the track.
+    position: usize,
+    /// Ticks accumulated since the last event fired, carried over between `poll` calls.
+    ///
+    /// Consuming an event subtracts its delta time from this rather than resetting it to zero,
+    /// so a `tick_count` that doesn't evenly divide an event's delta time doesn't lose the
+    /// leftover ticks — they carry forward and are credited toward the next event instead of
+    /// letting rounding drift the track out of sync over a long poll.
+    elapsed_ticks: u32,
+    /// Set once this track's `EndOfTrack` has fired, or its events are exhausted without one.
+    end_of_track: bool,
+}
+
+/// Steps through a file's tracks by accumulated tick count rather than wall-clock time.
+///
+/// ### Example
+/// ```ignore
+/// let mut cursor = TickCursor::new(&midi.track_list);
+/// loop {
+///     // `tick_count` here might come from an external MIDI clock, a fixed-step renderer, etc.
+///     let due = cursor.poll(tick_count);
+///     // send `due`'s bytes to wherever they need to go
+///     if cursor.is_finished() {
+///         break;
+///     }
+/// }
+/// ```
+pub struct TickCursor<'a> {
+    tracks: &'a [Track],
+    progress: Vec<TrackProgress>,
+}
+
+impl<'a> TickCursor<'a> {
+    /// Build a cursor over `tracks`, positioned at the start of every track.
+    ///
+    /// ### Arguments
+    /// * `tracks` The file's parsed tracks, in order
+    pub fn new(tracks: &'a [Track]) -> Self {
+        let progress = tracks
+            .iter()
+            .map(|_| TrackProgress {
+                position: 0,
+                elapsed_ticks: 0,
+                end_of_track: false,
+            })
+            .collect();
+
+        Self { tracks, progress }
+    }
+
+    /// Advance every track by `tick_count` ticks, returning the `to_bytes()` payload of every
+    /// event across every track that has come due since the last `poll`, in track order.
+    ///
+    /// ### Arguments
+    /// * `tick_count` How many ticks have elapsed since the last `poll` call
+    ///
+    /// ### Returns
+    /// The payloads of every event that has come due since the last `poll`
+    pub fn poll(&mut self, tick_count: u32) -> Vec<Vec<u8>> {
+        let mut due = Vec::new();
+
+        for (track, progress) in self.tracks.iter().zip(self.progress.iter_mut()) {
+            if progress.end_of_track {
+                continue;
+            }
+
+            progress.elapsed_ticks += tick_count;
+
+            while let Some(serializable_event) = track.events.get(progress.position) {
+                let event = &serializable_event.0;
+                if event.get_delta_time() > progress.elapsed_ticks {
+                    break;
+                }
+
+                progress.elapsed_ticks -= event.get_delta_time();
+                progress.position += 1;
+
+                if matches!(event, EventKind::EndOfTrack(_)) {
+                    progress.end_of_track = true;
+                }
+
+                if let Ok(bytes) = event.to_bytes() {
+                    due.push(bytes);
+                }
+            }
+
+            if progress.position >= track.events.len() {
+                progress.end_of_track = true;
+            }
+        }
+
+        due
+    }
+
+    /// Whether every track has fired its `EndOfTrack` (or run out of events without one).
+    pub fn is_finished(&self) -> bool {
+        self.progress.iter().all(|progress| progress.end_of_track)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        events::{EventKind, FromBytes, SerializableEvent},
+        messages::NoteOnEvent,
+        metadata::TimeDivision,
+        metaevents::EndOfTrack,
+    };
+
+    fn track_with(events: Vec<EventKind>) -> Track {
+        Track {
+            events: events.into_iter().map(SerializableEvent).collect(),
+            track_size: 0,
+        }
+    }
+
+    #[test]
+    fn poll_fires_events_once_their_delta_time_is_reached() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        let note_on = NoteOnEvent::from_bytes(&[0x90, 60, 100], 96, division, 120).unwrap();
+        let track = track_with(vec![EventKind::NoteOn(note_on)]);
+        let mut cursor = TickCursor::new(&[track]);
+
+        assert!(cursor.poll(50).is_empty());
+        let due = cursor.poll(46);
+        assert_eq!(due.len(), 1);
+    }
+
+    #[test]
+    fn poll_carries_leftover_ticks_forward_instead_of_dropping_them() {
+        let division = TimeDivision::PulsesPerQuarterNote(96);
+        let first = NoteOnEvent::from_bytes(&[0x90, 60, 100], 10, division, 120).unwrap();
+        let second = NoteOnEvent::from_bytes(&[0x90, 64, 100], 10, division, 120).unwrap();
+        let track = track_with(vec![EventKind::NoteOn(first), EventKind::NoteOn(second)]);
+        let mut cursor = TickCursor::new(&[track]);
+
+        // 7 ticks per poll never lands exactly on a multiple of 10, but the 2 events 10 ticks
+        // apart should still both have fired once the third poll reaches tick 21, if leftover
+        // ticks aren't discarded.
+        let mut due = cursor.poll(7);
+        due.extend(cursor.poll(7));
+        due.extend(cursor.poll(7));
+
+        assert_eq!(due.len(), 2);
+    }
+
+    #[test]
+    fn is_finished_once_end_of_track_fires() {
+        let track = track_with(vec![EventKind::EndOfTrack(EndOfTrack::new().unwrap())]);
+        let mut cursor = TickCursor::new(&[track]);
+
+        assert!(!cursor.is_finished());
+        cursor.poll(0);
+        assert!(cursor.is_finished());
+    }
+}
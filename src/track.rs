@@ -1,10 +1,10 @@
 use log::debug;
 
-#[cfg(feature = "json")]
-use serde::Serialize;
+#[cfg(feature = "use_serde")]
+use serde::{Serialize, Deserialize};
 
 use crate::{
-    events::{dispatch_from_bytes, Event, SerializableEvent},
+    events::{require_len, Event, EventKind, FromBytes, SerializableEvent},
     metadata::TimeDivision,
     metaevents::{
         from_bytes_to_vlq, CopyRightNoticeEvent, CuePointEvent, EndOfTrack, InstrumentNameEvent,
@@ -13,13 +13,14 @@ use crate::{
         TrackNameEvent, MidiPortEvent,
     },
     midi_error::{MidiError, ParseError},
-    TimeSignature, messages::{NoteOnEvent, EVENT_MASK, NoteOffEvent, ControlChangeEvent, PolyphonicKeyPressureEvent, ProgramChangeEvent, ChannelPressureEvent, PitchBendChangeEvent}, print_file_contents, is_msb_zero,
+    system_exclusive::{SystemExclusiveEvent, SYSTEM_EXCLUSIVE_BYTE, SYSTEM_EXCLUSIVE_ESCAPE_BYTE},
+    TimeSignature, messages::{NoteOnEvent, EVENT_MASK, CHANNEL_MASK, NoteOffEvent, ControlChangeEvent, PolyphonicKeyPressureEvent, ProgramChangeEvent, ChannelPressureEvent, PitchBendChangeEvent}, print_file_contents, is_msb_zero,
 };
 
 const TRACK_HEADER_BYTES: u32 = u32::from_be_bytes([0x4D, 0x54, 0x72, 0x6B]);
 
-#[derive(Debug)]
-#[cfg_attr(feature = "json", derive(Serialize))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 pub struct Track {
     pub events: Vec<SerializableEvent>,
     pub track_size: u32,
@@ -35,6 +36,7 @@ impl Track {
         // Validate that this is the start of a track
         debug!("The track data is as follows");
         print_file_contents(data);
+        require_len(data, 8, "Track Header")?;
         validate_track_header_chunk_bytes(u32::from_be_bytes([
             data[0], data[1], data[2], data[3],
         ]))?;
@@ -44,6 +46,7 @@ impl Track {
         debug!("The track size is {}", track_size);
 
         // The new data is from byte 8 to the size of the track
+        require_len(&data[8..], track_size as usize, "Track Body")?;
         let data = &data[8..(track_size + 8) as usize];
 
         // Set up an empty event list
@@ -51,8 +54,14 @@ impl Track {
 
         let mut position = 0;
 
-        let mut running_status = 0;
-        let mut channel = 0;
+        // The most recently seen channel-voice status byte, or `None` if the last event was a
+        // System/Meta event (which cancels running status).
+        let mut running_status: Option<u8> = None;
+
+        // The tempo in effect for whichever event is parsed next. A `SetTempoEvent` updates this
+        // so every later event's `time_duration` is computed from the tempo actually in effect at
+        // that point, rather than the tempo the track started with.
+        let mut tempo = tempo;
 
         while position < track_size {
 
@@ -63,30 +72,56 @@ impl Track {
 
             print_file_contents(&data[position as usize..]);
 
-            // If the two bytes next to each other both have their MSB as 0 that means they are data bytes a part of the current running status
-            if is_msb_zero(data[position as usize]) && is_msb_zero(data[(position + 1) as usize]) && running_status != 0 {
-                debug!("Byte {:02X} and byte {:02X} are probably data bytes to the current running status {:2X}", data[position as usize], data[(position + 1) as usize], running_status);
-
-                // We should instead get the event with the running status with the correct data and then increment the position by two and then continue the loop
-                let event = parse_running_status_data(running_status, channel, data, delta_time, time_division, tempo)?;
+            let status_candidate = data[position as usize];
+
+            let event = if is_msb_zero(status_candidate) {
+                // The status byte was omitted: this is the MIDI running-status optimization, so
+                // the current byte is actually the first data byte of the last channel-voice
+                // status seen.
+                let status = running_status.ok_or_else(|| {
+                    MidiError::ParseError(ParseError::InvalidEventBytes(String::from(
+                        "Data byte seen with no running status in effect",
+                    )))
+                })?;
+                let channel = status & CHANNEL_MASK;
+                debug!("Byte {:02X} is a data byte for running status {:02X}", status_candidate, status);
+
+                let event = parse_running_status_data(status, channel, &data[position as usize..], delta_time, time_division, tempo)?;
                 debug!("Added {} to the event list with the data bytes!", event.get_event_name());
 
-                events.push(SerializableEvent(event));
-
-                position += 2;
-                continue;
-            }
-
-            let event = parse_event(data[position as usize], &data[position as usize..], delta_time, time_division, tempo)?;
-            position += event.get_event_size() as u32;
-
-            debug!("Got {} event!", event.get_event_name());
-            debug!("The event is {} bytes long", event.get_event_size());
-
-            if event.is_running_status_allowed() {
-                debug!("The event supports the running status");
-                running_status = event.event_type();
-                channel = event.get_channel();
+                // `get_event_size` reports the full event size including the status byte, but
+                // that byte was omitted from `data` here, so only the data bytes actually read
+                // need to be consumed.
+                position += (event.get_event_size() as u32).saturating_sub(1);
+                event
+            } else {
+                let event = parse_event(status_candidate, &data[position as usize..], delta_time, time_division, tempo)?;
+
+                // `Event::get_event_size` is capped to `u8`, which truncates for a SysEx payload
+                // at or beyond 253 bytes; `SystemExclusiveEvent::total_size` carries the real,
+                // untruncated length instead.
+                position += match &event {
+                    EventKind::SystemExclusive(sysex) => sysex.total_size(),
+                    _ => event.get_event_size() as u32,
+                };
+
+                debug!("Got {} event!", event.get_event_name());
+                debug!("The event is {} bytes long", event.get_event_size());
+
+                // Meta events and sysex cancel running status; channel-voice events that opt in
+                // via `is_running_status_allowed` establish it for subsequent data-byte-only
+                // events.
+                running_status = if event.is_running_status_allowed() {
+                    Some(status_candidate)
+                } else {
+                    None
+                };
+
+                event
+            };
+
+            if let EventKind::SetTempo(set_tempo) = &event {
+                tempo = set_tempo.tempo.round() as u32;
             }
 
             events.push(SerializableEvent(event));
@@ -102,7 +137,62 @@ impl Track {
         ))
     }
 
-    pub fn get_track_list(data: &[u8]) -> Result<Vec<Self>, MidiError> {
+    /// Serialize this track back into its `MTrk` chunk bytes: the `MTrk` id, the big-endian body
+    /// length, and the VLQ-delta-time-prefixed bytes of every event in order. If the track's last
+    /// event isn't an `EndOfTrack`, one is appended so the emitted chunk is always a valid `MTrk`.
+    ///
+    /// ### Returns
+    /// The full `MTrk` chunk, ready to be written out or concatenated after an `MThd` header
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MidiError> {
+        let mut body = serialize_events(self.events.iter().map(|event| &event.0 as &dyn Event))?;
+
+        let ends_with_end_of_track =
+            matches!(self.events.last(), Some(SerializableEvent(EventKind::EndOfTrack(_))));
+        if !ends_with_end_of_track {
+            body.extend_from_slice(&EndOfTrack::new()?.to_bytes_delta_time()?);
+        }
+
+        let mut bytes = Vec::with_capacity(8 + body.len());
+        bytes.extend_from_slice(&TRACK_HEADER_BYTES.to_be_bytes());
+        bytes.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&body);
+
+        Ok(bytes)
+    }
+
+    /// Same as [`Track::to_bytes`], but streams the `MTrk` chunk straight to `w` instead of
+    /// returning it as an owned `Vec`.
+    ///
+    /// The chunk body is still assembled in a local buffer first, since `MTrk`'s length prefix
+    /// has to be known before the body can be written. Each event writes its bytes via
+    /// [`Event::write_to`]; only the events that override it (`SystemExclusiveEvent` and
+    /// `SequencerSpecificEvent`) write straight into the body, while every other event goes
+    /// through the default, which still builds its own `Vec` with `to_bytes_delta_time` and
+    /// copies it in.
+    #[cfg(feature = "std")]
+    pub fn write_to<W: std::io::Write>(&self, w: &mut W) -> Result<(), MidiError> {
+        let mut body: Vec<u8> = Vec::with_capacity(self.events.len() * 4);
+        for event in &self.events {
+            event.0.write_to(&mut body)?;
+        }
+
+        let ends_with_end_of_track =
+            matches!(self.events.last(), Some(SerializableEvent(EventKind::EndOfTrack(_))));
+        if !ends_with_end_of_track {
+            EndOfTrack::new()?.write_to(&mut body)?;
+        }
+
+        w.write_all(&TRACK_HEADER_BYTES.to_be_bytes())?;
+        w.write_all(&(body.len() as u32).to_be_bytes())?;
+        w.write_all(&body)?;
+
+        Ok(())
+    }
+
+    pub fn get_track_list(
+        data: &[u8],
+        time_division: TimeDivision,
+    ) -> Result<Vec<Self>, MidiError> {
         // Create an empty list of tracks
         let mut track_list: Vec<Self> = Vec::new();
 
@@ -116,7 +206,7 @@ impl Track {
 
         while position < data.len() {
             // Now create each track; We give each track the tempo and time signature in case it overrides the overall time signature and/or tempo
-            let (track, tempo_override, time_signature_override) = Self::new(&data[position..], TimeDivision::PulsesPerQuarterNote(96), tempo, time_signature)?;
+            let (track, tempo_override, time_signature_override) = Self::new(&data[position..], time_division, tempo, time_signature)?;
             tempo = tempo_override;
             time_signature = time_signature_override;
             position += (track.track_size + 8) as usize;
@@ -130,103 +220,95 @@ impl Track {
     }
 }
 
-fn parse_event(status_byte: u8, data: &[u8], delta_time: u32, time_division: TimeDivision, tempo: u32) -> Result<Box<dyn Event>, MidiError> {
+/// Serialize a sequence of events into an `MTrk` chunk's body (no id or length prefix), appending
+/// each event's `to_bytes_delta_time()` bytes into one shared buffer. Each
+/// `to_bytes_delta_time()` call still returns its own `Vec`, which is copied into that buffer.
+///
+/// The output buffer is pre-sized using an events-per-byte heuristic: with running status in
+/// play, real MIDI data averages a little over 3 bytes/event, so `event_count * 4` comfortably
+/// avoids reallocating as bytes are appended, at the cost of a few wasted bytes of capacity.
+///
+/// ### Arguments
+/// * `events` The events to serialize, in order
+///
+/// ### Returns
+/// The concatenated delta-time-prefixed bytes of every event
+pub(crate) fn serialize_events<'a>(
+    events: impl ExactSizeIterator<Item = &'a dyn Event>,
+) -> Result<Vec<u8>, MidiError> {
+    let mut bytes = Vec::with_capacity(events.len() * 4);
+
+    for event in events {
+        bytes.extend_from_slice(&event.to_bytes_delta_time()?);
+    }
+
+    Ok(bytes)
+}
+
+pub(crate) fn parse_event(status_byte: u8, data: &[u8], delta_time: u32, time_division: TimeDivision, tempo: u32) -> Result<EventKind, MidiError> {
     match status_byte {
         0xFF => parse_meta_event(data, delta_time, time_division, tempo),
+        SYSTEM_EXCLUSIVE_BYTE | SYSTEM_EXCLUSIVE_ESCAPE_BYTE => Ok(EventKind::SystemExclusive(
+            SystemExclusiveEvent::from_bytes(data, delta_time, time_division, tempo)?,
+        )),
         _ => {
                 let event_type = (status_byte & EVENT_MASK) >> 4;
                 match event_type {
-                    0x9 => dispatch_from_bytes::<NoteOnEvent>(data, delta_time, time_division, tempo),
-                    0x8 => dispatch_from_bytes::<NoteOffEvent>(data, delta_time, time_division, tempo),
-                    0xA => dispatch_from_bytes::<PolyphonicKeyPressureEvent>(data, delta_time, time_division, tempo),
-                    0xB => dispatch_from_bytes::<ControlChangeEvent>(data, delta_time, time_division, tempo),
-                    0xC => dispatch_from_bytes::<ProgramChangeEvent>(data, delta_time, time_division, tempo),
-                    0xD => dispatch_from_bytes::<ChannelPressureEvent>(data, delta_time, time_division, tempo),
-                    0xE => dispatch_from_bytes::<PitchBendChangeEvent>(data, delta_time, time_division, tempo),
+                    0x9 => Ok(EventKind::NoteOn(NoteOnEvent::from_bytes(data, delta_time, time_division, tempo)?)),
+                    0x8 => Ok(EventKind::NoteOff(NoteOffEvent::from_bytes(data, delta_time, time_division, tempo)?)),
+                    0xA => Ok(EventKind::PolyphonicKeyPressure(PolyphonicKeyPressureEvent::from_bytes(data, delta_time, time_division, tempo)?)),
+                    0xB => Ok(EventKind::ControlChange(ControlChangeEvent::from_bytes(data, delta_time, time_division, tempo)?)),
+                    0xC => Ok(EventKind::ProgramChange(ProgramChangeEvent::from_bytes(data, delta_time, time_division, tempo)?)),
+                    0xD => Ok(EventKind::ChannelPressure(ChannelPressureEvent::from_bytes(data, delta_time, time_division, tempo)?)),
+                    0xE => Ok(EventKind::PitchBendChange(PitchBendChangeEvent::from_bytes(data, delta_time, time_division, tempo)?)),
                     _ => Err(MidiError::ParseError(ParseError::NotImplemented(format!("Event {:02X} is not implemented!", event_type)))),
                 }
             }
     }
 }
 
-fn parse_running_status_data(running_status: u8, channel: u8, data: &[u8], delta_time: u32, time_division: TimeDivision, tempo: u32) -> Result<Box<dyn Event>, MidiError> {
+fn parse_running_status_data(running_status: u8, channel: u8, data: &[u8], delta_time: u32, time_division: TimeDivision, tempo: u32) -> Result<EventKind, MidiError> {
     match running_status >> 4 {
-        0x9 => Ok(Box::new(NoteOnEvent::new_from_status(data, delta_time, channel, time_division, tempo)?)),
-        0xB => Ok(Box::new(ControlChangeEvent::new_from_status(data, channel, time_division, tempo)?)),
+        0x8 => Ok(EventKind::NoteOff(NoteOffEvent::new_from_status(data, delta_time, channel, time_division, tempo)?)),
+        0x9 => Ok(EventKind::NoteOn(NoteOnEvent::new_from_status(data, delta_time, channel, time_division, tempo)?)),
+        0xA => Ok(EventKind::PolyphonicKeyPressure(PolyphonicKeyPressureEvent::new_from_status(data, delta_time, channel, time_division, tempo)?)),
+        0xB => Ok(EventKind::ControlChange(ControlChangeEvent::new_from_status(data, channel, time_division, tempo)?)),
+        0xC => Ok(EventKind::ProgramChange(ProgramChangeEvent::new_from_status(data, delta_time, channel, time_division, tempo)?)),
+        0xD => Ok(EventKind::ChannelPressure(ChannelPressureEvent::new_from_status(data, delta_time, channel, time_division, tempo)?)),
+        0xE => Ok(EventKind::PitchBendChange(PitchBendChangeEvent::new_from_status(data, delta_time, channel, time_division, tempo)?)),
         _ => Err(MidiError::ParseError(ParseError::InvalidEventBytes(format!("Invalid running status!")))),
     }
 }
 
-fn parse_meta_event(
+pub(crate) fn parse_meta_event(
     data: &[u8],
     delta_time: u32,
     ticks_per_quarter_note: TimeDivision,
     tempo: u32,
-) -> Result<Box<dyn Event>, MidiError> {
-    let returned_event: Box<dyn Event> = match data[1] {
-        0x00 => {
-            dispatch_from_bytes::<SequenceNumber>(data, delta_time, ticks_per_quarter_note, tempo)
-        }
-        0x01 => dispatch_from_bytes::<TextEvent>(data, delta_time, ticks_per_quarter_note, tempo),
-        0x02 => dispatch_from_bytes::<CopyRightNoticeEvent>(
-            data,
-            delta_time,
-            ticks_per_quarter_note,
-            tempo,
-        ),
-        0x03 => {
-            dispatch_from_bytes::<TrackNameEvent>(data, delta_time, ticks_per_quarter_note, tempo)
-        }
-        0x04 => dispatch_from_bytes::<InstrumentNameEvent>(
-            data,
-            delta_time,
-            ticks_per_quarter_note,
-            tempo,
-        ),
-        0x05 => dispatch_from_bytes::<LyricEvent>(data, delta_time, ticks_per_quarter_note, tempo),
-        0x06 => dispatch_from_bytes::<MarkerEvent>(data, delta_time, ticks_per_quarter_note, tempo),
-        0x07 => {
-            dispatch_from_bytes::<CuePointEvent>(data, delta_time, ticks_per_quarter_note, tempo)
-        }
-        0x20 => dispatch_from_bytes::<MidiChannelPrefixEvent>(
-            data,
-            delta_time,
-            ticks_per_quarter_note,
-            tempo,
-        ),
-        0x21 => dispatch_from_bytes::<MidiPortEvent>(data, delta_time, ticks_per_quarter_note, tempo),
-        0x2F => dispatch_from_bytes::<EndOfTrack>(data, delta_time, ticks_per_quarter_note, tempo),
-        0x51 => {
-            dispatch_from_bytes::<SetTempoEvent>(data, delta_time, ticks_per_quarter_note, tempo)
-        }
-        0x54 => {
-            dispatch_from_bytes::<SMPTEOffsetEvent>(data, delta_time, ticks_per_quarter_note, tempo)
-        }
-        0x58 => dispatch_from_bytes::<TimeSignatureEvent>(
-            data,
-            delta_time,
-            ticks_per_quarter_note,
-            tempo,
-        ),
-        0x59 => dispatch_from_bytes::<KeySignatureEvent>(
-            data,
-            delta_time,
-            ticks_per_quarter_note,
-            tempo,
-        ),
-        0x7F => dispatch_from_bytes::<SequencerSpecificEvent>(
-            data,
-            delta_time,
-            ticks_per_quarter_note,
-            tempo,
-        ),
-        _ => {
-            return Err(MidiError::ParseError(ParseError::NotImplemented(
-                String::from(format!("{:02X} is not an implemented Meta Event", data[1])),
-            )))
-        }
-    }?;
-    Ok(returned_event)
+) -> Result<EventKind, MidiError> {
+    require_len(data, 2, "Meta Event")?;
+
+    match data[1] {
+        0x00 => Ok(EventKind::SequenceNumber(SequenceNumber::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        0x01 => Ok(EventKind::Text(TextEvent::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        0x02 => Ok(EventKind::CopyRightNotice(CopyRightNoticeEvent::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        0x03 => Ok(EventKind::TrackName(TrackNameEvent::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        0x04 => Ok(EventKind::InstrumentName(InstrumentNameEvent::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        0x05 => Ok(EventKind::Lyric(LyricEvent::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        0x06 => Ok(EventKind::Marker(MarkerEvent::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        0x07 => Ok(EventKind::CuePoint(CuePointEvent::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        0x20 => Ok(EventKind::MidiChannelPrefix(MidiChannelPrefixEvent::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        0x21 => Ok(EventKind::MidiPort(MidiPortEvent::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        0x2F => Ok(EventKind::EndOfTrack(EndOfTrack::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        0x51 => Ok(EventKind::SetTempo(SetTempoEvent::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        0x54 => Ok(EventKind::SMPTEOffset(SMPTEOffsetEvent::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        0x58 => Ok(EventKind::TimeSignature(TimeSignatureEvent::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        0x59 => Ok(EventKind::KeySignature(KeySignatureEvent::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        0x7F => Ok(EventKind::SequencerSpecific(SequencerSpecificEvent::from_bytes(data, delta_time, ticks_per_quarter_note, tempo)?)),
+        _ => Err(MidiError::ParseError(ParseError::NotImplemented(
+            String::from(format!("{:02X} is not an implemented Meta Event", data[1])),
+        ))),
+    }
 }
 
 fn validate_track_header_chunk_bytes(track_header_chunk_bytes: u32) -> Result<(), MidiError> {